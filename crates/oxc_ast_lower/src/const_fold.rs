@@ -0,0 +1,218 @@
+use oxc_hir::hir;
+use oxc_span::Span;
+
+use crate::AstLower;
+
+/// `ToInt32` per the ECMAScript spec: truncate toward zero, reduce modulo 2^32, then
+/// reinterpret the unsigned 32-bit result as signed.
+fn to_int32(value: f64) -> i32 {
+    if !value.is_finite() || value == 0.0 {
+        return 0;
+    }
+    let truncated = value.trunc();
+    let modulo = truncated.rem_euclid(4_294_967_296.0); // 2^32
+    if modulo >= 2_147_483_648.0 {
+        (modulo - 4_294_967_296.0) as i32
+    } else {
+        modulo as i32
+    }
+}
+
+fn as_number(expr: &hir::Expression) -> Option<f64> {
+    match expr {
+        hir::Expression::NumberLiteral(lit) => Some(lit.value),
+        _ => None,
+    }
+}
+
+fn as_string<'a>(expr: &hir::Expression<'a>) -> Option<&hir::StringLiteral<'a>> {
+    match expr {
+        hir::Expression::StringLiteral(lit) => Some(lit),
+        _ => None,
+    }
+}
+
+fn is_truthy(expr: &hir::Expression) -> Option<bool> {
+    match expr {
+        hir::Expression::NullLiteral(_) => Some(false),
+        hir::Expression::BooleanLiteral(lit) => Some(lit.value),
+        hir::Expression::NumberLiteral(lit) => Some(lit.value != 0.0 && !lit.value.is_nan()),
+        hir::Expression::StringLiteral(lit) => Some(!lit.value.is_empty()),
+        _ => None,
+    }
+}
+
+/// Rejects a fold whose result isn't a finite `f64` -- `folded.to_string()` would otherwise
+/// produce Rust's `"inf"`/`"-inf"`/`"NaN"` spellings as `raw`, none of which are valid JS numeric
+/// literals, so a non-finite result is left unfolded rather than emitting source that doesn't
+/// parse.
+fn finite(value: f64) -> Option<f64> {
+    value.is_finite().then_some(value)
+}
+
+/// Folds a binary numeric operator, following JS `f64` semantics.
+fn fold_numeric(operator: hir::BinaryOperator, left: f64, right: f64) -> Option<f64> {
+    use hir::BinaryOperator as Op;
+    Some(match operator {
+        Op::Addition => left + right,
+        Op::Subtraction => left - right,
+        Op::Multiplication => left * right,
+        Op::Division => left / right,
+        Op::Remainder => left % right,
+        Op::Exponential => {
+            if right.fract() == 0.0 && right.abs() < i32::MAX as f64 {
+                left.powi(right as i32)
+            } else {
+                left.powf(right)
+            }
+        }
+        Op::ShiftLeft => f64::from(to_int32(left).wrapping_shl(to_int32(right) as u32 & 31)),
+        Op::ShiftRight => f64::from(to_int32(left).wrapping_shr(to_int32(right) as u32 & 31)),
+        Op::ShiftRightZeroFill => {
+            f64::from((to_int32(left) as u32).wrapping_shr(to_int32(right) as u32 & 31))
+        }
+        Op::BitwiseOR => f64::from(to_int32(left) | to_int32(right)),
+        Op::BitwiseXOR => f64::from(to_int32(left) ^ to_int32(right)),
+        Op::BitwiseAnd => f64::from(to_int32(left) & to_int32(right)),
+        _ => return None,
+    })
+}
+
+impl<'a> AstLower<'a> {
+    /// Attempts to constant-fold `left <operator> right` into a single literal, preserving
+    /// `span` (the original outer span of the binary expression) on the result. Returns `None`
+    /// when the operands aren't both literals, or the operator isn't one we know how to fold.
+    pub(crate) fn try_fold_binary_expression(
+        &mut self,
+        span: Span,
+        left: &hir::Expression<'a>,
+        operator: hir::BinaryOperator,
+        right: &hir::Expression<'a>,
+    ) -> Option<hir::Expression<'a>> {
+        if !self.options.constant_fold {
+            return None;
+        }
+        if let (Some(left), Some(right)) = (as_string(left), as_string(right)) {
+            if operator == hir::BinaryOperator::Addition {
+                let value = format!("{}{}", left.value, right.value);
+                let lit = self.hir.string_literal(span, value.into());
+                return Some(self.hir.literal_string_expression(lit));
+            }
+        }
+        let (left, right) = (as_number(left)?, as_number(right)?);
+        let folded = finite(fold_numeric(operator, left, right)?)?;
+        let raw = self.hir.alloc_str(&folded.to_string());
+        let lit = self.hir.number_literal(span, folded, raw, hir::NumberBase::Decimal);
+        Some(self.hir.literal_number_expression(lit))
+    }
+
+    /// Attempts to constant-fold a unary `operator` applied to a literal `argument`, preserving
+    /// `span` on the result.
+    pub(crate) fn try_fold_unary_expression(
+        &mut self,
+        span: Span,
+        operator: hir::UnaryOperator,
+        argument: &hir::Expression<'a>,
+    ) -> Option<hir::Expression<'a>> {
+        if !self.options.constant_fold {
+            return None;
+        }
+        match operator {
+            hir::UnaryOperator::LogicalNot => {
+                let value = !is_truthy(argument)?;
+                let lit = self.hir.boolean_literal(span, value);
+                Some(self.hir.literal_boolean_expression(lit))
+            }
+            hir::UnaryOperator::UnaryNegation | hir::UnaryOperator::UnaryPlus => {
+                let value = as_number(argument)?;
+                let value = if operator == hir::UnaryOperator::UnaryNegation { -value } else { value };
+                let value = finite(value)?;
+                let raw = self.hir.alloc_str(&value.to_string());
+                let lit = self.hir.number_literal(span, value, raw, hir::NumberBase::Decimal);
+                Some(self.hir.literal_number_expression(lit))
+            }
+            hir::UnaryOperator::BitwiseNot => {
+                let value = as_number(argument)?;
+                let value = finite(f64::from(!to_int32(value)))?;
+                let raw = self.hir.alloc_str(&value.to_string());
+                let lit = self.hir.number_literal(span, value, raw, hir::NumberBase::Decimal);
+                Some(self.hir.literal_number_expression(lit))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxc_allocator::Allocator;
+    use oxc_hir::hir;
+    use oxc_span::Span;
+
+    use crate::{AstLower, AstLowerOptions};
+
+    fn number<'a>(lower: &mut AstLower<'a>, value: f64) -> hir::Expression<'a> {
+        let raw = lower.hir.alloc_str(&value.to_string());
+        let lit = lower.hir.number_literal(Span::default(), value, raw, hir::NumberBase::Decimal);
+        lower.hir.literal_number_expression(lit)
+    }
+
+    fn folded_value(expr: hir::Expression) -> f64 {
+        match expr {
+            hir::Expression::NumberLiteral(lit) => lit.value,
+            other => panic!("expected a folded number literal, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn folds_finite_binary_result() {
+        let allocator = Allocator::default();
+        let options = AstLowerOptions { constant_fold: true, ..AstLowerOptions::default() };
+        let mut lower = AstLower::new_with_options(&allocator, options);
+        let left = number(&mut lower, 1.0);
+        let right = number(&mut lower, 2.0);
+        let folded = lower
+            .try_fold_binary_expression(Span::default(), &left, hir::BinaryOperator::Addition, &right)
+            .expect("1 + 2 should fold");
+        assert_eq!(folded_value(folded), 3.0);
+    }
+
+    #[test]
+    fn skips_folding_a_non_finite_binary_result() {
+        let allocator = Allocator::default();
+        let options = AstLowerOptions { constant_fold: true, ..AstLowerOptions::default() };
+        let mut lower = AstLower::new_with_options(&allocator, options);
+        let left = number(&mut lower, 1.0);
+        let right = number(&mut lower, 0.0);
+        // 1 / 0 is `Infinity`, which has no valid JS numeric literal spelling -- must stay unfolded.
+        assert!(lower
+            .try_fold_binary_expression(Span::default(), &left, hir::BinaryOperator::Division, &right)
+            .is_none());
+    }
+
+    #[test]
+    fn folds_finite_unary_result() {
+        let allocator = Allocator::default();
+        let options = AstLowerOptions { constant_fold: true, ..AstLowerOptions::default() };
+        let mut lower = AstLower::new_with_options(&allocator, options);
+        let argument = number(&mut lower, 5.0);
+        let folded = lower
+            .try_fold_unary_expression(Span::default(), hir::UnaryOperator::UnaryNegation, &argument)
+            .expect("negation of a finite number should fold");
+        assert_eq!(folded_value(folded), -5.0);
+    }
+
+    #[test]
+    fn skips_folding_a_non_finite_unary_result() {
+        let allocator = Allocator::default();
+        let options = AstLowerOptions { constant_fold: true, ..AstLowerOptions::default() };
+        let mut lower = AstLower::new_with_options(&allocator, options);
+        // Negation/bitwise-not can't themselves manufacture a non-finite value from a finite
+        // input, but a non-finite operand (as could reach here from an earlier, already-unfolded
+        // expression) must still be rejected rather than re-spelled as Rust's `"-inf"`.
+        let argument = number(&mut lower, f64::INFINITY);
+        assert!(lower
+            .try_fold_unary_expression(Span::default(), hir::UnaryOperator::UnaryNegation, &argument)
+            .is_none());
+    }
+}
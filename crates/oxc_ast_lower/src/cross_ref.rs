@@ -0,0 +1,89 @@
+//! An opt-in "save-analysis" style dump of every definition and reference [`crate::AstLower`]
+//! produces, modeled on rustc's old save-analysis `dump_visitor`: a flat defs/refs cross-reference
+//! keyed by span and [`crate::HirId`] that an external IDE or indexing tool can consume without
+//! understanding the HIR's shape.
+//!
+//! Recording only happens when [`crate::AstLowerOptions::emit_cross_reference`] is set --
+//! building the document allocates a `String` per binding, which a lowering pass that isn't
+//! feeding an indexer shouldn't have to pay for.
+//!
+//! A [`Reference`] exists for exactly one reason a [`Definition`] alone can't cover: an import or
+//! export specifier draws a link between two names (`local`/`imported`, `local`/`exported`) that
+//! the HIR flattens away once the specifier is lowered into its component parts. Everything else
+//! -- a class name, a function name, a variable binding, a parameter -- is a [`Definition`] on its
+//! own with no counterpart name to link it to.
+
+use serde::Serialize;
+
+use oxc_span::Span;
+
+/// The category of binding a [`Definition`] introduces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefinitionKind {
+    Import,
+    Export,
+    Class,
+    Function,
+    Variable,
+    Parameter,
+}
+
+/// One binding introduced while lowering: an import specifier's local name, a class or function
+/// name, a variable binding, or a parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct Definition {
+    pub id: u32,
+    pub span_start: u32,
+    pub span_end: u32,
+    pub name: String,
+    pub kind: DefinitionKind,
+}
+
+/// The local/imported or local/exported link a single import or export specifier draws between
+/// two names.
+#[derive(Debug, Clone, Serialize)]
+pub struct Reference {
+    pub id: u32,
+    pub span_start: u32,
+    pub span_end: u32,
+    pub local: String,
+    pub other: String,
+}
+
+/// The accumulated defs/refs for one lowered module. Serializes to the `{ "defs": [...], "refs":
+/// [...] }` document described in the module doc comment.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CrossReference {
+    defs: std::vec::Vec<Definition>,
+    refs: std::vec::Vec<Reference>,
+}
+
+impl CrossReference {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push_definition(&mut self, id: u32, span: Span, name: String, kind: DefinitionKind) {
+        self.defs.push(Definition { id, span_start: span.start, span_end: span.end, name, kind });
+    }
+
+    pub(crate) fn push_reference(&mut self, id: u32, span: Span, local: String, other: String) {
+        self.refs.push(Reference { id, span_start: span.start, span_end: span.end, local, other });
+    }
+
+    #[must_use]
+    pub fn defs(&self) -> &[Definition] {
+        &self.defs
+    }
+
+    #[must_use]
+    pub fn refs(&self) -> &[Reference] {
+        &self.refs
+    }
+
+    /// Serializes this cross-reference to the JSON document external tooling consumes.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
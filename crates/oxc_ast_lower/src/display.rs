@@ -0,0 +1,339 @@
+//! Pretty-printing lowered `hir` nodes back to JS/TS source, the way rust-analyzer's `HirDisplay`
+//! renders hover text and signature detail from its HIR. A [`HirDisplay`] impl writes through a
+//! [`HirFormatter`] rather than a bare `fmt::Formatter` so it can ask
+//! [`HirFormatter::signature_only`] and skip a function/class/static-block body it wasn't asked
+//! to render -- useful for completion/hover detail, where the body is noise and the signature is
+//! the whole point.
+//!
+//! Only the node types this crate's lowering chunk touches get an impl: import/export
+//! declarations, `Function`, `Class`, and `VariableDeclaration`. Anything nested inside a body
+//! (statements, most expressions) isn't rendered at all in full mode -- there's no general
+//! statement printer in this crate yet, so a non-signature-only render shows `{ ... }` rather
+//! than reconstructing the body verbatim.
+
+use std::fmt;
+
+use oxc_hir::hir;
+
+/// Sink a [`HirDisplay`] impl writes into. Wraps a plain `fmt::Write` (usually a `fmt::Formatter`
+/// from a [`HirDisplayWrapper`]'s `Display` impl) with the one piece of out-of-band state a
+/// renderer needs: whether to omit bodies.
+pub struct HirFormatter<'a> {
+    sink: &'a mut dyn fmt::Write,
+    signature_only: bool,
+}
+
+impl<'a> HirFormatter<'a> {
+    /// When set, a function/class/static-block body is omitted entirely rather than rendered as
+    /// `{ ... }`.
+    #[must_use]
+    pub fn signature_only(&self) -> bool {
+        self.signature_only
+    }
+
+    /// Builds a formatter over an arbitrary sink, for a caller like
+    /// [`crate::signature::SignatureString`] that wants to reuse a `write_*` helper from this
+    /// module without going through a [`HirDisplayWrapper`]'s `Display` impl.
+    pub(crate) fn for_signature_string(sink: &'a mut dyn fmt::Write) -> Self {
+        Self { sink, signature_only: true }
+    }
+}
+
+impl fmt::Write for HirFormatter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.sink.write_str(s)
+    }
+}
+
+/// Implemented by every `hir` node type this crate knows how to render back to source.
+pub trait HirDisplay {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result;
+
+    /// A full, body-included rendering, usable anywhere `{}` formatting is wanted.
+    fn display(&self) -> HirDisplayWrapper<'_, Self>
+    where
+        Self: Sized,
+    {
+        HirDisplayWrapper { node: self, signature_only: false }
+    }
+
+    /// The same rendering with bodies omitted -- the shape editor hover/completion detail wants.
+    fn display_signature(&self) -> HirDisplayWrapper<'_, Self>
+    where
+        Self: Sized,
+    {
+        HirDisplayWrapper { node: self, signature_only: true }
+    }
+}
+
+/// `fmt::Display` adapter returned by [`HirDisplay::display`]/[`HirDisplay::display_signature`].
+pub struct HirDisplayWrapper<'a, T: ?Sized> {
+    node: &'a T,
+    signature_only: bool,
+}
+
+impl<T: HirDisplay + ?Sized> fmt::Display for HirDisplayWrapper<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut hf = HirFormatter { sink: f, signature_only: self.signature_only };
+        self.node.hir_fmt(&mut hf)
+    }
+}
+
+/// Renders a string literal's value back as a double-quoted source string. Doesn't re-escape
+/// embedded quotes/backslashes -- every caller's `value` comes from already-valid source text, so
+/// the only thing this needs to restore is the quoting `StringLiteral` stripped off.
+fn write_quoted(f: &mut HirFormatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"{value}\"")
+}
+
+fn module_export_name_str(name: &hir::ModuleExportName) -> &str {
+    match name {
+        hir::ModuleExportName::Identifier(ident) => &ident.name,
+        hir::ModuleExportName::StringLiteral(lit) => &lit.value,
+    }
+}
+
+fn write_module_export_name(f: &mut HirFormatter<'_>, name: &hir::ModuleExportName) -> fmt::Result {
+    match name {
+        hir::ModuleExportName::Identifier(ident) => write!(f, "{}", ident.name),
+        hir::ModuleExportName::StringLiteral(lit) => write_quoted(f, &lit.value),
+    }
+}
+
+/// A `BindingPattern`'s surface shape: a destructured property shows the pattern it binds (its
+/// value, not its key), nested patterns are rendered recursively, and default values are
+/// dropped, so `{ a, b }`/`[a, b]` parameters render as written.
+pub(crate) fn write_binding_pattern(f: &mut HirFormatter<'_>, pattern: &hir::BindingPattern) -> fmt::Result {
+    match pattern {
+        hir::BindingPattern::BindingIdentifier(ident) => write!(f, "{}", ident.name),
+        hir::BindingPattern::ObjectPattern(pat) => {
+            write!(f, "{{ ")?;
+            for (i, property) in pat.properties.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                match property {
+                    hir::ObjectPatternProperty::Property(property) => {
+                        write_binding_pattern(f, &property.value)?;
+                    }
+                    hir::ObjectPatternProperty::RestElement(rest) => {
+                        write!(f, "...")?;
+                        write_binding_pattern(f, &rest.argument)?;
+                    }
+                }
+            }
+            write!(f, " }}")
+        }
+        hir::BindingPattern::ArrayPattern(pat) => {
+            write!(f, "[")?;
+            for (i, element) in pat.elements.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                if let Some(element) = element {
+                    write_binding_pattern(f, element)?;
+                }
+            }
+            write!(f, "]")
+        }
+        hir::BindingPattern::RestElement(rest) => {
+            write!(f, "...")?;
+            write_binding_pattern(f, &rest.argument)
+        }
+        hir::BindingPattern::AssignmentPattern(pat) => write_binding_pattern(f, &pat.left),
+    }
+}
+
+pub(crate) fn write_formal_parameters(f: &mut HirFormatter<'_>, params: &hir::FormalParameters) -> fmt::Result {
+    for (i, param) in params.items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write_binding_pattern(f, &param.pattern)?;
+    }
+    Ok(())
+}
+
+impl<'a> HirDisplay for hir::Function<'a> {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result {
+        if self.r#async {
+            write!(f, "async ")?;
+        }
+        write!(f, "function")?;
+        if self.generator {
+            write!(f, "*")?;
+        }
+        if let Some(id) = &self.id {
+            write!(f, " {}", id.name)?;
+        } else {
+            write!(f, " ")?;
+        }
+        write!(f, "(")?;
+        write_formal_parameters(f, &self.params)?;
+        write!(f, ")")?;
+        if f.signature_only() {
+            return Ok(());
+        }
+        match &self.body {
+            Some(_) => write!(f, " {{ ... }}"),
+            None => write!(f, ";"),
+        }
+    }
+}
+
+impl<'a> HirDisplay for hir::Class<'a> {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result {
+        write!(f, "class")?;
+        if let Some(id) = &self.id {
+            write!(f, " {}", id.name)?;
+        }
+        if self.super_class.is_some() {
+            write!(f, " extends ...")?;
+        }
+        if f.signature_only() {
+            return Ok(());
+        }
+        write!(f, " {{ ... }}")
+    }
+}
+
+impl<'a> HirDisplay for hir::VariableDeclaration<'a> {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result {
+        let keyword = match self.kind {
+            hir::VariableDeclarationKind::Var => "var",
+            hir::VariableDeclarationKind::Let => "let",
+            hir::VariableDeclarationKind::Const => "const",
+        };
+        write!(f, "{keyword} ")?;
+        for (i, declarator) in self.declarations.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write_binding_pattern(f, &declarator.id)?;
+            if !f.signature_only() && declarator.init.is_some() {
+                write!(f, " = ...")?;
+            }
+        }
+        write!(f, ";")
+    }
+}
+
+impl<'a> HirDisplay for hir::ImportDeclaration<'a> {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result {
+        write!(f, "import ")?;
+        if !self.specifiers.is_empty() {
+            let mut wrote_any = false;
+            let mut i = 0;
+            while i < self.specifiers.len() {
+                if wrote_any {
+                    write!(f, ", ")?;
+                }
+                match &self.specifiers[i] {
+                    hir::ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                        write!(f, "{}", spec.local.name)?;
+                        i += 1;
+                    }
+                    hir::ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                        write!(f, "* as {}", spec.local.name)?;
+                        i += 1;
+                    }
+                    hir::ImportDeclarationSpecifier::ImportSpecifier(_) => {
+                        write!(f, "{{ ")?;
+                        let mut wrote_one = false;
+                        while let Some(hir::ImportDeclarationSpecifier::ImportSpecifier(spec)) =
+                            self.specifiers.get(i)
+                        {
+                            if wrote_one {
+                                write!(f, ", ")?;
+                            }
+                            write_module_export_name(f, &spec.imported)?;
+                            if module_export_name_str(&spec.imported) != spec.local.name.as_ref() {
+                                write!(f, " as {}", spec.local.name)?;
+                            }
+                            wrote_one = true;
+                            i += 1;
+                        }
+                        write!(f, " }}")?;
+                    }
+                }
+                wrote_any = true;
+            }
+            write!(f, " from ")?;
+        }
+        write_quoted(f, &self.source.value)?;
+        if let Some(assertions) = &self.assertions {
+            write_import_assertions(f, assertions)?;
+        }
+        write!(f, ";")
+    }
+}
+
+impl<'a> HirDisplay for hir::ExportAllDeclaration<'a> {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result {
+        write!(f, "export *")?;
+        if let Some(exported) = &self.exported {
+            write!(f, " as ")?;
+            write_module_export_name(f, exported)?;
+        }
+        write!(f, " from ")?;
+        write_quoted(f, &self.source.value)?;
+        if let Some(assertions) = &self.assertions {
+            write_import_assertions(f, assertions)?;
+        }
+        write!(f, ";")
+    }
+}
+
+impl<'a> HirDisplay for hir::ExportNamedDeclaration<'a> {
+    fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> fmt::Result {
+        write!(f, "export ")?;
+        if !self.specifiers.is_empty() || self.declaration.is_none() {
+            write!(f, "{{ ")?;
+            for (i, specifier) in self.specifiers.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write_module_export_name(f, &specifier.local)?;
+                if module_export_name_str(&specifier.local) != module_export_name_str(&specifier.exported) {
+                    write!(f, " as ")?;
+                    write_module_export_name(f, &specifier.exported)?;
+                }
+            }
+            write!(f, " }}")?;
+            if let Some(source) = &self.source {
+                write!(f, " from ")?;
+                write_quoted(f, &source.value)?;
+            }
+            write!(f, ";")
+        } else if let Some(declaration) = &self.declaration {
+            // A bare `export function f() {}`/`export class C {}`/`export const x = 1;` has no
+            // specifier list to render -- delegate to the declaration itself.
+            match declaration {
+                hir::Declaration::VariableDeclaration(decl) => decl.hir_fmt(f),
+                hir::Declaration::FunctionDeclaration(func) => func.hir_fmt(f),
+                hir::Declaration::ClassDeclaration(class) => class.hir_fmt(f),
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn write_import_assertions(f: &mut HirFormatter<'_>, assertions: &oxc_allocator::Vec<hir::ImportAttribute>) -> fmt::Result {
+    if assertions.is_empty() {
+        return Ok(());
+    }
+    write!(f, " assert {{ ")?;
+    for (i, attribute) in assertions.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        match &attribute.key {
+            hir::ImportAttributeKey::Identifier(ident) => write!(f, "{}", ident.name)?,
+            hir::ImportAttributeKey::StringLiteral(lit) => write_quoted(f, &lit.value)?,
+        }
+        write!(f, ": ")?;
+        write_quoted(f, &attribute.value.value)?;
+    }
+    write!(f, " }}")
+}
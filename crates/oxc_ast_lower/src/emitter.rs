@@ -0,0 +1,515 @@
+//! A flat stack-machine bytecode emitter for the `hir` this crate produces, parallel to
+//! `AstLower` itself: where `AstLower` turns `ast` into `hir`, `Emitter` turns `hir` into a
+//! linear `Opcode` buffer a VM can step through. Expressions push exactly one value; statements
+//! leave the stack exactly as balanced as they found it. Control flow is lowered with
+//! forward/back-patched jumps rather than a tree-shaped interpreter loop.
+
+use std::collections::HashMap;
+
+use oxc_hir::hir;
+use oxc_span::{GetSpan, Span};
+
+/// A pool entry referenced by `Opcode::PushConst`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Opcode {
+    PushConst(u32),
+    PushUndefined,
+    Pop,
+    Dup,
+    GetIdentifier(u32),
+    GetProperty(u32),
+    GetPropertyComputed,
+    BinaryOp(hir::BinaryOperator),
+    UnaryOp(hir::UnaryOperator),
+    Call(u32),
+    /// Unconditional jump; operand is an absolute instruction index, patched after emission.
+    Jump(u32),
+    JumpIfFalse(u32),
+    JumpIfTrue(u32),
+    /// Jumps when the top of stack is `null` or `undefined`, without popping it.
+    JumpIfNullish(u32),
+    Return,
+}
+
+/// Owns the opcode buffer plus the constant pool and interned atom set (identifier/property
+/// names) that opcodes index into.
+#[derive(Debug, Default)]
+pub struct InstructionWriter {
+    code: std::vec::Vec<Opcode>,
+    constants: std::vec::Vec<Constant>,
+    atoms: std::vec::Vec<String>,
+    atom_indices: HashMap<String, u32>,
+    /// `code[i]`'s originating span, recorded in the same order opcodes are pushed, so a VM can
+    /// report positions without every opcode carrying a `Span` itself.
+    spans: std::vec::Vec<Span>,
+}
+
+impl InstructionWriter {
+    fn push(&mut self, opcode: Opcode, span: Span) -> usize {
+        let index = self.code.len();
+        self.code.push(opcode);
+        self.spans.push(span);
+        index
+    }
+
+    fn patch_jump_target(&mut self, at: usize, target: u32) {
+        match &mut self.code[at] {
+            Opcode::Jump(t) | Opcode::JumpIfFalse(t) | Opcode::JumpIfTrue(t) | Opcode::JumpIfNullish(t) => {
+                *t = target;
+            }
+            _ => unreachable!("patched instruction was not a jump"),
+        }
+    }
+
+    fn constant(&mut self, constant: Constant) -> u32 {
+        if let Some(index) = self.constants.iter().position(|c| *c == constant) {
+            return u32::try_from(index).expect("constant pool overflow");
+        }
+        self.constants.push(constant);
+        u32::try_from(self.constants.len() - 1).expect("constant pool overflow")
+    }
+
+    fn atom(&mut self, name: &str) -> u32 {
+        if let Some(&index) = self.atom_indices.get(name) {
+            return index;
+        }
+        let index = u32::try_from(self.atoms.len()).expect("atom table overflow");
+        self.atoms.push(name.to_string());
+        self.atom_indices.insert(name.to_string(), index);
+        index
+    }
+
+    fn here(&self) -> u32 {
+        u32::try_from(self.code.len()).expect("program too large")
+    }
+}
+
+/// The flat output of a successful [`Emitter::emit_program`] call.
+#[derive(Debug)]
+pub struct EmitResult {
+    pub code: std::vec::Vec<Opcode>,
+    pub constants: std::vec::Vec<Constant>,
+    pub atoms: std::vec::Vec<String>,
+    pub spans: std::vec::Vec<Span>,
+}
+
+/// Per-loop jump-patch lists so `break`/`continue` can resolve to the end/continuation of their
+/// enclosing loop even though that address isn't known until the loop finishes emitting.
+#[derive(Debug, Default)]
+struct LoopLabels {
+    break_jumps: std::vec::Vec<usize>,
+    continue_jumps: std::vec::Vec<usize>,
+}
+
+#[derive(Default)]
+pub struct Emitter {
+    writer: InstructionWriter,
+    loop_stack: std::vec::Vec<LoopLabels>,
+}
+
+impl Emitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn emit_program(mut self, program: &hir::Program) -> EmitResult {
+        self.emit_statements(&program.body);
+        EmitResult {
+            code: self.writer.code,
+            constants: self.writer.constants,
+            atoms: self.writer.atoms,
+            spans: self.writer.spans,
+        }
+    }
+
+    fn emit_statements(&mut self, stmts: &oxc_allocator::Vec<hir::Statement>) {
+        for stmt in stmts {
+            self.emit_statement(stmt);
+        }
+    }
+
+    fn emit_statement(&mut self, stmt: &hir::Statement) {
+        match stmt {
+            hir::Statement::ExpressionStatement(stmt) => {
+                self.emit_expression(&stmt.expression);
+                self.writer.push(Opcode::Pop, stmt.span);
+            }
+            hir::Statement::BlockStatement(stmt) => self.emit_statements(&stmt.body),
+            hir::Statement::IfStatement(stmt) => {
+                self.emit_expression(&stmt.test);
+                let jump_to_alternate = self.writer.push(Opcode::JumpIfFalse(0), stmt.span);
+                self.emit_statement(&stmt.consequent);
+                if let Some(alternate) = &stmt.alternate {
+                    let jump_to_end = self.writer.push(Opcode::Jump(0), stmt.span);
+                    let alternate_start = self.writer.here();
+                    self.writer.patch_jump_target(jump_to_alternate, alternate_start);
+                    self.emit_statement(alternate);
+                    let end = self.writer.here();
+                    self.writer.patch_jump_target(jump_to_end, end);
+                } else {
+                    let end = self.writer.here();
+                    self.writer.patch_jump_target(jump_to_alternate, end);
+                }
+            }
+            hir::Statement::WhileStatement(stmt) => {
+                let loop_start = self.writer.here();
+                self.loop_stack.push(LoopLabels::default());
+                self.emit_expression(&stmt.test);
+                let exit_jump = self.writer.push(Opcode::JumpIfFalse(0), stmt.span);
+                self.emit_statement(&stmt.body);
+                self.writer.push(Opcode::Jump(loop_start), stmt.span);
+                let end = self.writer.here();
+                self.writer.patch_jump_target(exit_jump, end);
+                self.finish_loop(end, loop_start);
+            }
+            hir::Statement::ReturnStatement(stmt) => {
+                match &stmt.argument {
+                    Some(argument) => self.emit_expression(argument),
+                    None => {
+                        self.writer.push(Opcode::PushUndefined, stmt.span);
+                    }
+                }
+                self.writer.push(Opcode::Return, stmt.span);
+            }
+            hir::Statement::BreakStatement(stmt) => {
+                let jump = self.writer.push(Opcode::Jump(0), stmt.span);
+                if let Some(labels) = self.loop_stack.last_mut() {
+                    labels.break_jumps.push(jump);
+                }
+            }
+            hir::Statement::ContinueStatement(stmt) => {
+                let jump = self.writer.push(Opcode::Jump(0), stmt.span);
+                if let Some(labels) = self.loop_stack.last_mut() {
+                    labels.continue_jumps.push(jump);
+                }
+            }
+            // Declarations, for/for-in/for-of, switch, try, and module items aren't on the
+            // critical path for this emitter yet; they're left for a follow-up once the VM side
+            // has somewhere to put bindings and exception handlers.
+            _ => {}
+        }
+    }
+
+    fn finish_loop(&mut self, break_target: u32, continue_target: u32) {
+        if let Some(labels) = self.loop_stack.pop() {
+            for jump in labels.break_jumps {
+                self.writer.patch_jump_target(jump, break_target);
+            }
+            for jump in labels.continue_jumps {
+                self.writer.patch_jump_target(jump, continue_target);
+            }
+        }
+    }
+
+    fn emit_expression(&mut self, expr: &hir::Expression) {
+        match expr {
+            hir::Expression::NumberLiteral(lit) => {
+                let index = self.writer.constant(Constant::Number(lit.value));
+                self.writer.push(Opcode::PushConst(index), lit.span);
+            }
+            hir::Expression::StringLiteral(lit) => {
+                let index = self.writer.constant(Constant::String(lit.value.to_string()));
+                self.writer.push(Opcode::PushConst(index), lit.span);
+            }
+            hir::Expression::BooleanLiteral(lit) => {
+                let index = self.writer.constant(Constant::Boolean(lit.value));
+                self.writer.push(Opcode::PushConst(index), lit.span);
+            }
+            hir::Expression::NullLiteral(lit) => {
+                let index = self.writer.constant(Constant::Null);
+                self.writer.push(Opcode::PushConst(index), lit.span);
+            }
+            hir::Expression::Identifier(ident) => {
+                let atom = self.writer.atom(&ident.name);
+                self.writer.push(Opcode::GetIdentifier(atom), ident.span);
+            }
+            hir::Expression::BinaryExpression(expr) => {
+                self.emit_expression(&expr.left);
+                self.emit_expression(&expr.right);
+                self.writer.push(Opcode::BinaryOp(expr.operator), expr.span);
+            }
+            hir::Expression::UnaryExpression(expr) => {
+                self.emit_expression(&expr.argument);
+                self.writer.push(Opcode::UnaryOp(expr.operator), expr.span);
+            }
+            hir::Expression::LogicalExpression(expr) => self.emit_logical(expr),
+            hir::Expression::ConditionalExpression(expr) => {
+                self.emit_expression(&expr.test);
+                let jump_to_alternate = self.writer.push(Opcode::JumpIfFalse(0), expr.span);
+                self.emit_expression(&expr.consequent);
+                let jump_to_end = self.writer.push(Opcode::Jump(0), expr.span);
+                let alternate_start = self.writer.here();
+                self.writer.patch_jump_target(jump_to_alternate, alternate_start);
+                self.emit_expression(&expr.alternate);
+                let end = self.writer.here();
+                self.writer.patch_jump_target(jump_to_end, end);
+            }
+            hir::Expression::CallExpression(expr) => {
+                self.emit_expression(&expr.callee);
+                for argument in &expr.arguments {
+                    match argument {
+                        hir::Argument::Expression(expr) => self.emit_expression(expr),
+                        // A spread argument still counts toward `argc`, so it gets a placeholder.
+                        _ => {
+                            self.writer.push(Opcode::PushUndefined, expr.span);
+                        }
+                    }
+                }
+                let argc = u32::try_from(expr.arguments.len()).expect("too many arguments");
+                self.writer.push(Opcode::Call(argc), expr.span);
+            }
+            hir::Expression::MemberExpression(expr) => self.emit_member_expression(expr, Span::default()),
+            hir::Expression::ChainExpression(expr) => self.emit_chain_expression(expr),
+            hir::Expression::SequenceExpression(expr) => {
+                let last = expr.expressions.len().saturating_sub(1);
+                for (i, expr) in expr.expressions.iter().enumerate() {
+                    self.emit_expression(expr);
+                    if i != last {
+                        self.writer.push(Opcode::Pop, expr.span());
+                    }
+                }
+            }
+            // Anything not yet handled by the VM backend this emitter targets still needs to
+            // leave exactly one value on the stack, so push `undefined` as a placeholder rather
+            // than silently imbalancing the stack.
+            _ => {
+                self.writer.push(Opcode::PushUndefined, Span::default());
+            }
+        }
+    }
+
+    fn emit_logical(&mut self, expr: &hir::LogicalExpression) {
+        self.emit_expression(&expr.left);
+        match expr.operator {
+            hir::LogicalOperator::And => {
+                self.writer.push(Opcode::Dup, expr.span);
+                let short_circuit = self.writer.push(Opcode::JumpIfFalse(0), expr.span);
+                self.writer.push(Opcode::Pop, expr.span);
+                self.emit_expression(&expr.right);
+                let end = self.writer.here();
+                self.writer.patch_jump_target(short_circuit, end);
+            }
+            hir::LogicalOperator::Or => {
+                self.writer.push(Opcode::Dup, expr.span);
+                let short_circuit = self.writer.push(Opcode::JumpIfTrue(0), expr.span);
+                self.writer.push(Opcode::Pop, expr.span);
+                self.emit_expression(&expr.right);
+                let end = self.writer.here();
+                self.writer.patch_jump_target(short_circuit, end);
+            }
+            hir::LogicalOperator::Coalesce => {
+                // `JumpIfNullish` peeks without popping, so unlike `&&`/`||` there's nothing to
+                // duplicate: the not-nullish path just leaves `left` on the stack as-is, and the
+                // nullish path pops it itself before evaluating `right`.
+                let nullish = self.writer.push(Opcode::JumpIfNullish(0), expr.span);
+                let skip_right = self.writer.push(Opcode::Jump(0), expr.span);
+                let evaluate_right = self.writer.here();
+                self.writer.patch_jump_target(nullish, evaluate_right);
+                self.writer.push(Opcode::Pop, expr.span);
+                self.emit_expression(&expr.right);
+                let end = self.writer.here();
+                self.writer.patch_jump_target(skip_right, end);
+            }
+        }
+    }
+
+    fn emit_member_expression(&mut self, expr: &hir::MemberExpression, _span: Span) {
+        match expr {
+            hir::MemberExpression::ComputedMemberExpression(expr) => {
+                self.emit_expression(&expr.object);
+                self.emit_expression(&expr.expression);
+                self.writer.push(Opcode::GetPropertyComputed, expr.span);
+            }
+            hir::MemberExpression::StaticMemberExpression(expr) => {
+                self.emit_expression(&expr.object);
+                let atom = self.writer.atom(&expr.property.name);
+                self.writer.push(Opcode::GetProperty(atom), expr.span);
+            }
+            hir::MemberExpression::PrivateFieldExpression(expr) => {
+                self.emit_expression(&expr.object);
+                let atom = self.writer.atom(&expr.field.name);
+                self.writer.push(Opcode::GetProperty(atom), expr.span);
+            }
+        }
+    }
+
+    /// `a?.b.c` / `a.b?.()` desugar to: evaluate the chain's base once, walking outward through
+    /// every link (member access or call) in source order, and short-circuit the *whole chain*
+    /// to `undefined` the moment any `optional` link's object/callee turns out nullish -- not
+    /// just the one immediately after it. All of a chain's nullish guards jump to the same
+    /// shared short-circuit, which is what makes e.g. `a?.b.c` skip the `.c` read too when `a`
+    /// is nullish, instead of only guarding the outermost link.
+    fn emit_chain_expression(&mut self, expr: &hir::ChainExpression) {
+        let mut jumps = std::vec::Vec::new();
+        match &expr.expression {
+            hir::ChainElement::CallExpression(call) => {
+                self.emit_chain_link(&call.callee, &mut jumps);
+                self.emit_guarded_call_tail(call, &mut jumps);
+            }
+            hir::ChainElement::MemberExpression(member) => {
+                self.emit_chain_link(member_object(member), &mut jumps);
+                self.emit_guarded_member_tail(member, &mut jumps);
+            }
+        }
+        if jumps.is_empty() {
+            return;
+        }
+        let jump_to_end = self.writer.push(Opcode::Jump(0), Span::default());
+        let short_circuit = self.writer.here();
+        for jump in jumps {
+            self.writer.patch_jump_target(jump, short_circuit);
+        }
+        self.writer.push(Opcode::Pop, Span::default());
+        self.writer.push(Opcode::Pop, Span::default());
+        self.writer.push(Opcode::PushUndefined, Span::default());
+        let end = self.writer.here();
+        self.writer.patch_jump_target(jump_to_end, end);
+    }
+
+    /// Recursively emits one more level of a chain's base: a nested member/call link is itself
+    /// walked (so a non-optional outer link like `.c` in `a?.b.c` doesn't stop the walk before
+    /// reaching the optional `.b` underneath it), while anything else is just the chain's root
+    /// expression and is emitted directly.
+    fn emit_chain_link(&mut self, expr: &hir::Expression, jumps: &mut std::vec::Vec<usize>) {
+        match expr {
+            hir::Expression::MemberExpression(member) => {
+                self.emit_chain_link(member_object(member), jumps);
+                self.emit_guarded_member_tail(member, jumps);
+            }
+            hir::Expression::CallExpression(call) => {
+                self.emit_chain_link(&call.callee, jumps);
+                self.emit_guarded_call_tail(call, jumps);
+            }
+            other => self.emit_expression(other),
+        }
+    }
+
+    /// Emits `member`'s property read, assuming its object has already been pushed by the
+    /// caller. If this particular link is `optional`, guards it with a nullish check first and
+    /// records the (not-yet-patched) jump in `jumps` for the chain's shared short-circuit.
+    fn emit_guarded_member_tail(&mut self, member: &hir::MemberExpression, jumps: &mut std::vec::Vec<usize>) {
+        let span = member_span(member);
+        if member_optional(member) {
+            self.writer.push(Opcode::Dup, span);
+            jumps.push(self.writer.push(Opcode::JumpIfNullish(0), span));
+            self.writer.push(Opcode::Pop, span);
+        }
+        self.emit_member_tail(member);
+    }
+
+    /// Emits `call`'s invocation, assuming its callee has already been pushed by the caller. If
+    /// this particular link is `optional`, guards it with a nullish check first and records the
+    /// (not-yet-patched) jump in `jumps` for the chain's shared short-circuit.
+    fn emit_guarded_call_tail(&mut self, call: &hir::CallExpression, jumps: &mut std::vec::Vec<usize>) {
+        if call.optional {
+            self.writer.push(Opcode::Dup, call.span);
+            jumps.push(self.writer.push(Opcode::JumpIfNullish(0), call.span));
+            self.writer.push(Opcode::Pop, call.span);
+        }
+        for argument in &call.arguments {
+            match argument {
+                hir::Argument::Expression(expr) => self.emit_expression(expr),
+                // A spread argument still counts toward `argc`, so it gets a placeholder.
+                _ => {
+                    self.writer.push(Opcode::PushUndefined, call.span);
+                }
+            }
+        }
+        let argc = u32::try_from(call.arguments.len()).expect("too many arguments");
+        self.writer.push(Opcode::Call(argc), call.span);
+    }
+
+    /// Emits the property-read instruction for `member`, assuming its object has already been
+    /// pushed by the caller.
+    fn emit_member_tail(&mut self, member: &hir::MemberExpression) {
+        match member {
+            hir::MemberExpression::ComputedMemberExpression(expr) => {
+                self.emit_expression(&expr.expression);
+                self.writer.push(Opcode::GetPropertyComputed, expr.span);
+            }
+            hir::MemberExpression::StaticMemberExpression(expr) => {
+                let atom = self.writer.atom(&expr.property.name);
+                self.writer.push(Opcode::GetProperty(atom), expr.span);
+            }
+            hir::MemberExpression::PrivateFieldExpression(expr) => {
+                let atom = self.writer.atom(&expr.field.name);
+                self.writer.push(Opcode::GetProperty(atom), expr.span);
+            }
+        }
+    }
+}
+
+fn member_span(member: &hir::MemberExpression) -> Span {
+    match member {
+        hir::MemberExpression::ComputedMemberExpression(expr) => expr.span,
+        hir::MemberExpression::StaticMemberExpression(expr) => expr.span,
+        hir::MemberExpression::PrivateFieldExpression(expr) => expr.span,
+    }
+}
+
+fn member_optional(member: &hir::MemberExpression) -> bool {
+    match member {
+        hir::MemberExpression::ComputedMemberExpression(expr) => expr.optional,
+        hir::MemberExpression::StaticMemberExpression(expr) => expr.optional,
+        hir::MemberExpression::PrivateFieldExpression(expr) => expr.optional,
+    }
+}
+
+fn member_object(member: &hir::MemberExpression) -> &hir::Expression {
+    match member {
+        hir::MemberExpression::ComputedMemberExpression(expr) => &expr.object,
+        hir::MemberExpression::StaticMemberExpression(expr) => &expr.object,
+        hir::MemberExpression::PrivateFieldExpression(expr) => &expr.object,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxc_allocator::Allocator;
+    use oxc_hir::{hir, hir_builder::HirBuilder};
+    use oxc_span::Span;
+
+    use super::{Emitter, Opcode};
+
+    /// Builds `a?.b.c`: only the inner `.b` link is optional, the outer `.c` is not. A correct
+    /// emitter must still guard the inner link even though it isn't the outermost one.
+    fn build_a_optional_b_c<'a>(hir: &mut HirBuilder<'a>) -> hir::Program<'a> {
+        let a = hir.identifier_reference(Span::default(), "a".into());
+        let a_expr = hir.identifier_reference_expression(a);
+        let b_name = hir.identifier_name(Span::default(), "b".into());
+        let ab = hir.static_member_expression(Span::default(), a_expr, b_name, true);
+        let c_name = hir.identifier_name(Span::default(), "c".into());
+        let abc = hir.static_member_expression(Span::default(), ab, c_name, false);
+        let hir::Expression::MemberExpression(member) = abc else {
+            unreachable!("static_member_expression always returns a MemberExpression");
+        };
+        let chain = hir.chain_expression(Span::default(), hir::ChainElement::MemberExpression(member));
+        let stmt = hir.expression_statement(Span::default(), chain);
+        let mut statements = hir.new_vec_with_capacity(1);
+        statements.push(stmt);
+        let directives = hir.new_vec_with_capacity(0);
+        hir.program(Span::default(), directives, statements)
+    }
+
+    #[test]
+    fn guards_every_optional_link_in_a_chain_not_just_the_outermost() {
+        let allocator = Allocator::default();
+        let mut hir = HirBuilder::new(&allocator);
+        let program = build_a_optional_b_c(&mut hir);
+
+        let result = Emitter::new().emit_program(&program);
+
+        let nullish_guards =
+            result.code.iter().filter(|opcode| matches!(opcode, Opcode::JumpIfNullish(_))).count();
+        assert_eq!(nullish_guards, 1, "the optional `.b` link must be guarded even though `.c` isn't");
+    }
+}
@@ -0,0 +1,119 @@
+//! A lightweight index of a module's declaration-level surface -- imports, exports, and
+//! top-level functions/classes/variables -- built alongside the HIR as [`crate::AstLower`] walks
+//! a `Program`. Modeled on rust-analyzer's `item_tree`: each entry stores the owning [`Span`], a
+//! resolved name, and the [`HirId`] of the node it summarizes, rather than its own copy of the
+//! HIR subtree. A bundler's dependency graph or a dead-export pass can answer "what does this
+//! module import/export" straight from this, without re-walking the full HIR tree to find out.
+
+use std::vec::Vec;
+
+use oxc_span::{Atom, Span};
+
+use crate::HirId;
+
+/// Either shape an `ast::ModuleExportName` can take, resolved down to the name it carries. A
+/// lightweight stand-in for `hir::ModuleExportName` that doesn't need the full HIR node just to
+/// answer "what name does this import/export binding expose".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportName<'a> {
+    Identifier(Atom<'a>),
+    StringLiteral(Atom<'a>),
+}
+
+/// One binding introduced by an `import` declaration's specifier list.
+#[derive(Debug, Clone, Copy)]
+pub enum ImportBinding<'a> {
+    /// `import { imported as local }` (or `import { local }`, where `imported` and `local` name
+    /// the same binding).
+    Named { imported: ExportName<'a>, local: Atom<'a> },
+    /// `import local from "..."`.
+    Default { local: Atom<'a> },
+    /// `import * as local from "..."`.
+    Namespace { local: Atom<'a> },
+}
+
+/// One `import` declaration.
+#[derive(Debug, Clone)]
+pub struct ImportItem<'a> {
+    pub id: HirId,
+    pub span: Span,
+    pub source: Atom<'a>,
+    pub bindings: Vec<ImportBinding<'a>>,
+}
+
+/// One re-export surface: `export { ... } [from source]`, `export * [as name] from source`, or
+/// `export default ...`.
+#[derive(Debug, Clone)]
+pub enum ExportItem<'a> {
+    /// `export { local as exported, ... } [from source]`. `source` is `None` for a plain
+    /// re-export of local bindings, `Some` when re-exporting through this module from another.
+    Named { id: HirId, span: Span, source: Option<Atom<'a>>, specifiers: Vec<(ExportName<'a>, ExportName<'a>)> },
+    /// `export * [as exported] from source`.
+    All { id: HirId, span: Span, source: Atom<'a>, exported: Option<ExportName<'a>> },
+    /// `export default ...`.
+    Default { id: HirId, span: Span, exported: ExportName<'a> },
+}
+
+/// The kind of binding a [`TopLevelItem`] summarizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopLevelItemKind {
+    Function,
+    Class,
+    Variable,
+}
+
+/// A function, class, or variable binding declared directly in a module's top-level statement
+/// list (bare, or as the declaration of an `export`/`export default`) -- not the same kind of
+/// declaration nested inside a block, function, or class body, which this index doesn't track.
+#[derive(Debug, Clone, Copy)]
+pub struct TopLevelItem<'a> {
+    pub id: HirId,
+    pub span: Span,
+    pub kind: TopLevelItemKind,
+    /// `None` for a top-level variable declarator whose binding isn't a plain identifier (e.g.
+    /// `const { a, b } = obj;`) -- destructured top-level bindings aren't resolved into the index.
+    pub name: Option<Atom<'a>>,
+}
+
+/// The module-level summary populated by [`crate::AstLower`] while it lowers a `Program`: every
+/// import, every export surface, and every top-level function/class/variable declaration, each a
+/// cheap record rather than a copy of the HIR subtree it summarizes.
+#[derive(Debug, Default)]
+pub struct ItemTree<'a> {
+    imports: Vec<ImportItem<'a>>,
+    exports: Vec<ExportItem<'a>>,
+    top_level_items: Vec<TopLevelItem<'a>>,
+}
+
+impl<'a> ItemTree<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push_import(&mut self, item: ImportItem<'a>) {
+        self.imports.push(item);
+    }
+
+    pub(crate) fn push_export(&mut self, item: ExportItem<'a>) {
+        self.exports.push(item);
+    }
+
+    pub(crate) fn push_top_level_item(&mut self, item: TopLevelItem<'a>) {
+        self.top_level_items.push(item);
+    }
+
+    #[must_use]
+    pub fn imports(&self) -> &[ImportItem<'a>] {
+        &self.imports
+    }
+
+    #[must_use]
+    pub fn exports(&self) -> &[ExportItem<'a>] {
+        &self.exports
+    }
+
+    #[must_use]
+    pub fn top_level_items(&self) -> &[TopLevelItem<'a>] {
+        &self.top_level_items
+    }
+}
@@ -0,0 +1,407 @@
+use oxc_allocator::Box;
+use oxc_ast::ast;
+use oxc_hir::hir;
+use oxc_span::Span;
+
+use crate::{AstLower, JsxRuntime};
+
+/// A lowercase-leading identifier name (`div`, `span`, `my-element`) is a DOM intrinsic and
+/// lowers to a string; any other identifier name (`Foo`) is a reference to a user-defined
+/// component. Namespaced (`svg:rect`) and member (`Foo.Bar`) names never reach this check.
+fn is_intrinsic_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+}
+
+/// `key={...}` controls reconciliation. The automatic runtime takes it as a separate argument
+/// rather than inside `props`; the classic lowering passes it through like any other attribute.
+fn is_key_attribute(name: &ast::JSXAttributeName) -> bool {
+    matches!(name, ast::JSXAttributeName::Identifier(ident) if ident.name == "key")
+}
+
+impl<'a> AstLower<'a> {
+    fn lower_jsx_pragma_callee(&mut self, span: Span, pragma: &str) -> hir::Expression<'a> {
+        let mut parts = pragma.split('.');
+        let first = parts.next().unwrap_or(pragma);
+        let ident = self.hir.identifier_reference(span, first.into());
+        let mut expr = self.hir.identifier_reference_expression(ident);
+        for part in parts {
+            let name = self.hir.identifier_name(span, part.into());
+            let member = self.hir.static_member_expression(span, expr, name, false);
+            expr = self.hir.member_expression(member);
+        }
+        expr
+    }
+
+    pub(crate) fn lower_jsx_element(&mut self, elem: &ast::JSXElement<'a>) -> hir::Expression<'a> {
+        match self.options.jsx_runtime {
+            JsxRuntime::Classic => self.lower_jsx_element_classic(elem),
+            JsxRuntime::Automatic => self.lower_jsx_element_automatic(elem),
+        }
+    }
+
+    pub(crate) fn lower_jsx_fragment(&mut self, elem: &ast::JSXFragment<'a>) -> hir::Expression<'a> {
+        match self.options.jsx_runtime {
+            JsxRuntime::Classic => self.lower_jsx_fragment_classic(elem),
+            JsxRuntime::Automatic => self.lower_jsx_fragment_automatic(elem),
+        }
+    }
+
+    fn lower_jsx_element_classic(&mut self, elem: &ast::JSXElement<'a>) -> hir::Expression<'a> {
+        let pragma = self.options.jsx_pragma.clone();
+        let callee = self.lower_jsx_pragma_callee(self.lower_span(elem.span), &pragma);
+        let name = self.lower_jsx_element_name(&elem.opening_element.name);
+        let props = self.lower_jsx_attributes(&elem.opening_element.attributes, self.lower_span(elem.span));
+        let children = self.lower_jsx_children(&elem.children);
+
+        let mut arguments = self.hir.new_vec_with_capacity(2 + children.len());
+        arguments.push(hir::Argument::Expression(name));
+        arguments.push(hir::Argument::Expression(props));
+        for child in children {
+            arguments.push(hir::Argument::Expression(child));
+        }
+        self.hir.call_expression(self.lower_span(elem.span), callee, arguments, false)
+    }
+
+    fn lower_jsx_fragment_classic(&mut self, elem: &ast::JSXFragment<'a>) -> hir::Expression<'a> {
+        let pragma = self.options.jsx_pragma.clone();
+        let frag_pragma = self.options.jsx_pragma_frag.clone();
+        let callee = self.lower_jsx_pragma_callee(self.lower_span(elem.span), &pragma);
+        let frag = self.lower_jsx_pragma_callee(self.lower_span(elem.span), &frag_pragma);
+        let null = self.hir.literal_null_expression(self.hir.null_literal(self.lower_span(elem.span)));
+        let children = self.lower_jsx_children(&elem.children);
+
+        let mut arguments = self.hir.new_vec_with_capacity(2 + children.len());
+        arguments.push(hir::Argument::Expression(frag));
+        arguments.push(hir::Argument::Expression(null));
+        for child in children {
+            arguments.push(hir::Argument::Expression(child));
+        }
+        self.hir.call_expression(self.lower_span(elem.span), callee, arguments, false)
+    }
+
+    fn lower_jsx_element_automatic(&mut self, elem: &ast::JSXElement<'a>) -> hir::Expression<'a> {
+        let span = self.lower_span(elem.span);
+        let name = self.lower_jsx_element_name(&elem.opening_element.name);
+        let children = self.lower_jsx_children(&elem.children);
+        let is_static = children.len() > 1;
+        let (props, key) =
+            self.lower_jsx_props_automatic(&elem.opening_element.attributes, children, span);
+        self.build_jsx_automatic_call(span, name, props, key, is_static)
+    }
+
+    fn lower_jsx_fragment_automatic(&mut self, elem: &ast::JSXFragment<'a>) -> hir::Expression<'a> {
+        let span = self.lower_span(elem.span);
+        let frag_pragma = self.options.jsx_automatic_fragment.clone();
+        let name = self.lower_jsx_pragma_callee(span, &frag_pragma);
+        let children = self.lower_jsx_children(&elem.children);
+        let is_static = children.len() > 1;
+        let mut properties = self.hir.new_vec_with_capacity(1);
+        if let Some(property) = self.jsx_children_property(children, span) {
+            properties.push(property);
+        }
+        let props = self.hir.object_expression(span, properties, false);
+        self.build_jsx_automatic_call(span, name, props, None, is_static)
+    }
+
+    /// Builds the `props` object (attributes plus a folded-in `children`) and pulls `key` out
+    /// into its own return value, the automatic runtime's split between the two.
+    fn lower_jsx_props_automatic(
+        &mut self,
+        attributes: &oxc_allocator::Vec<'a, ast::JSXAttributeItem<'a>>,
+        children: std::vec::Vec<hir::Expression<'a>>,
+        span: Span,
+    ) -> (hir::Expression<'a>, Option<hir::Expression<'a>>) {
+        let mut key = None;
+        let mut properties = self.hir.new_vec_with_capacity(attributes.len() + 1);
+        for item in attributes {
+            match item {
+                ast::JSXAttributeItem::Attribute(attr) if is_key_attribute(&attr.name) => {
+                    key = attr.value.as_ref().map(|value| self.lower_jsx_attribute_value(value));
+                }
+                ast::JSXAttributeItem::Attribute(attr) => {
+                    properties.push(hir::ObjectProperty::Property(self.lower_jsx_attribute(attr)));
+                }
+                ast::JSXAttributeItem::SpreadAttribute(attr) => {
+                    properties
+                        .push(hir::ObjectProperty::SpreadProperty(self.lower_jsx_spread_attribute(attr)));
+                }
+            }
+        }
+        if let Some(property) = self.jsx_children_property(children, span) {
+            properties.push(property);
+        }
+        let props = self.hir.object_expression(span, properties, false);
+        (props, key)
+    }
+
+    /// `None` when there are no children (the automatic runtime omits the `children` prop
+    /// entirely rather than setting it to an empty/undefined value); a single expression for one
+    /// child; an array expression once there's more than one.
+    fn jsx_children_property(
+        &mut self,
+        children: std::vec::Vec<hir::Expression<'a>>,
+        span: Span,
+    ) -> Option<hir::ObjectProperty<'a>> {
+        if children.is_empty() {
+            return None;
+        }
+        let value = if children.len() == 1 {
+            children.into_iter().next().unwrap()
+        } else {
+            let mut elements = self.hir.new_vec_with_capacity(children.len());
+            for child in children {
+                elements.push(hir::ArrayExpressionElement::Expression(child));
+            }
+            self.hir.array_expression(span, elements, None)
+        };
+        let key = self.hir.identifier_name(span, "children".into());
+        let key = self.hir.property_key_identifier(key);
+        let value = hir::PropertyValue::Expression(value);
+        let property = self.hir.property(span, hir::PropertyKind::Init, key, value, false, false, false);
+        Some(hir::ObjectProperty::Property(property))
+    }
+
+    fn build_jsx_automatic_call(
+        &mut self,
+        span: Span,
+        name: hir::Expression<'a>,
+        props: hir::Expression<'a>,
+        key: Option<hir::Expression<'a>>,
+        is_static: bool,
+    ) -> hir::Expression<'a> {
+        let pragma = if is_static {
+            self.options.jsx_automatic_jsxs.clone()
+        } else {
+            self.options.jsx_automatic_jsx.clone()
+        };
+        let callee = self.lower_jsx_pragma_callee(span, &pragma);
+        let mut arguments = self.hir.new_vec_with_capacity(if key.is_some() { 3 } else { 2 });
+        arguments.push(hir::Argument::Expression(name));
+        arguments.push(hir::Argument::Expression(props));
+        if let Some(key) = key {
+            arguments.push(hir::Argument::Expression(key));
+        }
+        self.hir.call_expression(span, callee, arguments, false)
+    }
+
+    fn lower_jsx_element_name(&mut self, name: &ast::JSXElementName<'a>) -> hir::Expression<'a> {
+        match name {
+            ast::JSXElementName::Identifier(ident) => {
+                if is_intrinsic_name(&ident.name) {
+                    let lit = self.hir.string_literal(self.lower_span(ident.span), self.lower_ident(ident.name.clone()));
+                    self.hir.literal_string_expression(lit)
+                } else {
+                    let ident = self.hir.identifier_reference(self.lower_span(ident.span), self.lower_ident(ident.name.clone()));
+                    self.hir.identifier_reference_expression(ident)
+                }
+            }
+            ast::JSXElementName::NamespacedName(name) => {
+                let raw = format!("{}:{}", name.namespace.name, name.property.name);
+                let lit = self.hir.string_literal(self.lower_span(name.span), raw.into());
+                self.hir.literal_string_expression(lit)
+            }
+            ast::JSXElementName::MemberExpression(member) => {
+                self.lower_jsx_member_expression(member)
+            }
+        }
+    }
+
+    fn lower_jsx_member_expression(
+        &mut self,
+        member: &ast::JSXMemberExpression<'a>,
+    ) -> hir::Expression<'a> {
+        let object = match &member.object {
+            ast::JSXMemberExpressionObject::Identifier(ident) => {
+                let ident = self.hir.identifier_reference(self.lower_span(ident.span), self.lower_ident(ident.name.clone()));
+                self.hir.identifier_reference_expression(ident)
+            }
+            ast::JSXMemberExpressionObject::MemberExpression(member) => {
+                self.lower_jsx_member_expression(member)
+            }
+        };
+        let property =
+            self.hir.identifier_name(self.lower_span(member.property.span), self.lower_ident(member.property.name.clone()));
+        let member_expr = self.hir.static_member_expression(self.lower_span(member.span), object, property, false);
+        self.hir.member_expression(member_expr)
+    }
+
+    fn lower_jsx_attributes(
+        &mut self,
+        attributes: &oxc_allocator::Vec<'a, ast::JSXAttributeItem<'a>>,
+        span: Span,
+    ) -> hir::Expression<'a> {
+        if attributes.is_empty() {
+            return self.hir.literal_null_expression(self.hir.null_literal(span));
+        }
+        let properties = self.lower_vec(attributes, Self::lower_jsx_attribute_item);
+        self.hir.object_expression(span, properties, false)
+    }
+
+    fn lower_jsx_attribute_item(
+        &mut self,
+        item: &ast::JSXAttributeItem<'a>,
+    ) -> hir::ObjectProperty<'a> {
+        match item {
+            ast::JSXAttributeItem::Attribute(attr) => {
+                hir::ObjectProperty::Property(self.lower_jsx_attribute(attr))
+            }
+            ast::JSXAttributeItem::SpreadAttribute(attr) => {
+                hir::ObjectProperty::SpreadProperty(self.lower_jsx_spread_attribute(attr))
+            }
+        }
+    }
+
+    fn lower_jsx_attribute(&mut self, attribute: &ast::JSXAttribute<'a>) -> Box<'a, hir::Property<'a>> {
+        let key = match &attribute.name {
+            ast::JSXAttributeName::Identifier(ident) => {
+                self.hir.identifier_name(self.lower_span(ident.span), self.lower_ident(ident.name.clone()))
+            }
+            ast::JSXAttributeName::NamespacedName(name) => {
+                let raw = format!("{}:{}", name.namespace.name, name.property.name);
+                self.hir.identifier_name(self.lower_span(name.span), raw.into())
+            }
+        };
+        let key = self.hir.property_key_identifier(key);
+        let value = attribute.value.as_ref().map_or_else(
+            || self.hir.literal_boolean_expression(self.hir.boolean_literal(self.lower_span(attribute.span), true)),
+            |value| self.lower_jsx_attribute_value(value),
+        );
+        let value = hir::PropertyValue::Expression(value);
+        self.hir.property(
+            self.lower_span(attribute.span),
+            hir::PropertyKind::Init,
+            key,
+            value,
+            false,
+            false,
+            false,
+        )
+    }
+
+    fn lower_jsx_spread_attribute(
+        &mut self,
+        attribute: &ast::JSXSpreadAttribute<'a>,
+    ) -> Box<'a, hir::SpreadElement<'a>> {
+        let argument = self.lower_expression(&attribute.argument);
+        self.hir.spread_element(self.lower_span(attribute.span), argument)
+    }
+
+    fn lower_jsx_attribute_value(&mut self, value: &ast::JSXAttributeValue<'a>) -> hir::Expression<'a> {
+        match value {
+            ast::JSXAttributeValue::StringLiteral(lit) => {
+                let lit = self.lower_string_literal(lit);
+                self.hir.literal_string_expression(lit)
+            }
+            ast::JSXAttributeValue::ExpressionContainer(expr) => {
+                self.lower_jsx_expression_container(expr)
+            }
+            ast::JSXAttributeValue::Element(elem) => self.lower_jsx_element(elem),
+            ast::JSXAttributeValue::Fragment(elem) => self.lower_jsx_fragment(elem),
+        }
+    }
+
+    fn lower_jsx_expression_container(
+        &mut self,
+        expr: &ast::JSXExpressionContainer<'a>,
+    ) -> hir::Expression<'a> {
+        self.lower_jsx_expression(&expr.expression)
+    }
+
+    fn lower_jsx_expression(&mut self, expr: &ast::JSXExpression<'a>) -> hir::Expression<'a> {
+        match expr {
+            ast::JSXExpression::Expression(expr) => self.lower_expression(expr),
+            ast::JSXExpression::EmptyExpression(empty) => {
+                self.hir.literal_null_expression(self.hir.null_literal(self.lower_span(empty.span)))
+            }
+        }
+    }
+
+    fn lower_jsx_children(
+        &mut self,
+        children: &oxc_allocator::Vec<'a, ast::JSXChild<'a>>,
+    ) -> std::vec::Vec<hir::Expression<'a>> {
+        children.iter().filter_map(|child| self.lower_jsx_child(child)).collect()
+    }
+
+    fn lower_jsx_child(&mut self, child: &ast::JSXChild<'a>) -> Option<hir::Expression<'a>> {
+        match child {
+            ast::JSXChild::Text(text) => self.lower_jsx_text(text),
+            ast::JSXChild::Element(elem) => Some(self.lower_jsx_element(elem)),
+            ast::JSXChild::Fragment(elem) => Some(self.lower_jsx_fragment(elem)),
+            ast::JSXChild::ExpressionContainer(expr) => Some(self.lower_jsx_expression_container(expr)),
+            ast::JSXChild::Spread(spread) => Some(self.lower_jsx_spread_child(spread)),
+        }
+    }
+
+    fn lower_jsx_spread_child(&mut self, child: &ast::JSXSpreadChild<'a>) -> hir::Expression<'a> {
+        self.lower_expression(&child.expression)
+    }
+
+    /// Collapses JSX whitespace the way a browser/Babel would: lines that are entirely
+    /// whitespace are dropped, and the leading/trailing whitespace of a line is stripped only
+    /// where it's adjacent to a newline. Returns `None` if nothing is left.
+    fn lower_jsx_text(&mut self, text: &ast::JSXText) -> Option<hir::Expression<'a>> {
+        let value = clean_jsx_text(&text.value);
+        if value.is_empty() {
+            return None;
+        }
+        let lit = self.hir.string_literal(self.lower_span(text.span), value.into());
+        Some(self.hir.literal_string_expression(lit))
+    }
+}
+
+/// Implements JSX's actual whitespace rule: a newline (and the horizontal whitespace surrounding
+/// it) is what gets trimmed/collapsed, not whitespace in general. A single-line run with no
+/// newline at all (`<a> foo </a>`) keeps its surrounding spaces verbatim -- there's no line
+/// boundary to collapse -- while a line that's pure whitespace *between* two newlines is dropped,
+/// and real content lines join with a single space where their shared line break used to be.
+fn clean_jsx_text(raw: &str) -> String {
+    let lines: std::vec::Vec<&str> = raw.split('\n').collect();
+    if lines.len() == 1 {
+        return lines[0].to_string();
+    }
+    let last_non_empty = lines.iter().rposition(|line| !line.trim().is_empty());
+    let mut out = String::new();
+    for (i, &line) in lines.iter().enumerate() {
+        let mut line = line;
+        if i != 0 {
+            line = line.trim_start_matches([' ', '\t']);
+        }
+        if i != lines.len() - 1 {
+            line = line.trim_end_matches([' ', '\t']);
+        }
+        if line.is_empty() {
+            continue;
+        }
+        out.push_str(line);
+        if Some(i) != last_non_empty {
+            out.push(' ');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clean_jsx_text;
+
+    #[test]
+    fn keeps_single_line_whitespace_verbatim() {
+        assert_eq!(clean_jsx_text(" foo "), " foo ");
+    }
+
+    #[test]
+    fn trims_only_whitespace_adjacent_to_a_newline() {
+        assert_eq!(clean_jsx_text("  foo  \n  bar  "), "  foo bar  ");
+    }
+
+    #[test]
+    fn drops_lines_that_are_pure_whitespace() {
+        assert_eq!(clean_jsx_text("foo\n   \nbar"), "foo bar");
+    }
+
+    #[test]
+    fn drops_whitespace_only_input_entirely() {
+        assert_eq!(clean_jsx_text("  \n  \n  "), "");
+    }
+}
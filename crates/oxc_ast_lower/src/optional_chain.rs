@@ -0,0 +1,340 @@
+//! Desugars an optional chain (`a?.b.c?.()`) into plain HIR with no `optional` flags, gated
+//! behind [`crate::AstLowerOptions::desugar_optional_chaining`].
+//!
+//! Every optional link `base?.x` becomes `(tmp = base, tmp == null) ? undefined : tmp.x`, with
+//! `tmp` a fresh synthetic binding so `base` is evaluated exactly once. Links after an optional
+//! one are built as the *alternate* arm of that link's conditional rather than chained on
+//! afterwards, so an earlier nullish check short-circuits every later member/call access in the
+//! chain, matching `?.`'s actual short-circuit semantics (not just the immediately following
+//! access).
+
+use oxc_ast::ast;
+use oxc_hir::hir;
+use oxc_span::Span;
+
+use crate::AstLower;
+
+/// One member/call step of a chain, already lowered except for the `object`/`callee` it applies
+/// to (that's threaded through separately as the desugaring is built up).
+enum ChainLink<'a> {
+    StaticMember { span: Span, property: hir::IdentifierName, optional: bool },
+    ComputedMember { span: Span, expression: hir::Expression<'a>, optional: bool },
+    PrivateField { span: Span, field: hir::PrivateIdentifier, optional: bool },
+    Call { span: Span, arguments: oxc_allocator::Vec<'a, hir::Argument<'a>>, optional: bool },
+}
+
+/// A captured, source-position-independent shape for the property an optional method call
+/// (`o.m?.()`) reads off its receiver, reusable to rebuild the same access twice (see
+/// [`AstLower::rebuild_method_access`]) without holding onto the original (possibly
+/// effectful/non-`Clone`) `hir::Expression` object.
+enum MethodProperty<'a> {
+    Static(Span, oxc_span::Atom<'a>),
+    Private(Span, oxc_span::Atom<'a>),
+    Computed(oxc_span::Atom<'a>),
+}
+
+impl<'a> AstLower<'a> {
+    pub(crate) fn desugar_chain_expression(
+        &mut self,
+        expr: &ast::ChainExpression<'a>,
+    ) -> hir::Expression<'a> {
+        let mut links = std::vec::Vec::new();
+        let mut cur = match &expr.expression {
+            ast::ChainElement::CallExpression(call) => {
+                self.collect_call_link(call, &mut links);
+                &call.callee
+            }
+            ast::ChainElement::MemberExpression(member) => {
+                self.collect_member_link(member, &mut links);
+                member_object(member)
+            }
+        };
+        loop {
+            match cur {
+                ast::Expression::MemberExpression(member) => {
+                    self.collect_member_link(member, &mut links);
+                    cur = member_object(member);
+                }
+                ast::Expression::CallExpression(call) => {
+                    self.collect_call_link(call, &mut links);
+                    cur = &call.callee;
+                }
+                root => break self.finish(root, links),
+            }
+        }
+    }
+
+    fn collect_member_link(
+        &mut self,
+        member: &ast::MemberExpression<'a>,
+        links: &mut std::vec::Vec<ChainLink<'a>>,
+    ) {
+        let link = match member {
+            ast::MemberExpression::StaticMemberExpression(member) => ChainLink::StaticMember {
+                span: member.span,
+                property: self.lower_identifier_name(&member.property),
+                optional: member.optional,
+            },
+            ast::MemberExpression::ComputedMemberExpression(member) => ChainLink::ComputedMember {
+                span: member.span,
+                expression: self.lower_expression(&member.expression),
+                optional: member.optional,
+            },
+            ast::MemberExpression::PrivateFieldExpression(member) => ChainLink::PrivateField {
+                span: member.span,
+                field: self.lower_private_identifier(&member.field),
+                optional: member.optional,
+            },
+        };
+        links.push(link);
+    }
+
+    fn collect_call_link(
+        &mut self,
+        call: &ast::CallExpression<'a>,
+        links: &mut std::vec::Vec<ChainLink<'a>>,
+    ) {
+        let arguments = self.lower_vec(&call.arguments, Self::lower_argument);
+        links.push(ChainLink::Call { span: call.span, arguments, optional: call.optional });
+    }
+
+    /// `links` was collected outer-to-inner while walking down to the root; reverse it to
+    /// root-to-outer and fold each link onto the lowered root in source order.
+    fn finish(
+        &mut self,
+        root: &ast::Expression<'a>,
+        mut links: std::vec::Vec<ChainLink<'a>>,
+    ) -> hir::Expression<'a> {
+        links.reverse();
+        let root = self.lower_expression(root);
+        self.build_chain_tail(root, links)
+    }
+
+    fn build_chain_tail(
+        &mut self,
+        current: hir::Expression<'a>,
+        mut links: std::vec::Vec<ChainLink<'a>>,
+    ) -> hir::Expression<'a> {
+        if links.is_empty() {
+            return current;
+        }
+        let link = links.remove(0);
+        let span = chain_link_span(&link);
+        let optional = chain_link_optional(&link);
+        if !optional {
+            let access = self.apply_chain_link(current, link);
+            return self.build_chain_tail(access, links);
+        }
+
+        // `o.m?.()`: the call is optional, but its callee is a member access an earlier
+        // (non-optional) link already built. Falling through to the generic path below would
+        // hoist that whole `o.m` callee into a temp and invoke the temp directly (`_oc0()`),
+        // running the method with `this === undefined` instead of `o`. Binding the receiver
+        // into its own temp and rebuilding `tmp.m(...)` as the callee (see
+        // `build_optional_method_call`) keeps `this` correct instead.
+        match (current, link) {
+            (hir::Expression::MemberExpression(member), ChainLink::Call { arguments, .. }) => {
+                self.build_optional_method_call(span, *member, arguments, links)
+            }
+            (current, link) => self.build_optional_value_temp(span, current, link, links),
+        }
+    }
+
+    /// The general optional-link desugaring: `(tmp = current) == null ? undefined :
+    /// apply(tmp, link)`. Correct whenever evaluating `link` on `tmp` doesn't need to observe
+    /// anything about how `tmp` itself was produced -- i.e. every case except an optional call
+    /// whose callee is a member access (handled separately by
+    /// [`Self::build_optional_method_call`] so that case keeps its receiver).
+    fn build_optional_value_temp(
+        &mut self,
+        span: Span,
+        current: hir::Expression<'a>,
+        link: ChainLink<'a>,
+        links: std::vec::Vec<ChainLink<'a>>,
+    ) -> hir::Expression<'a> {
+        let tmp = self.next_temp_name();
+        let assign_ident = self.hir.identifier_reference(span, tmp.clone());
+        let assign_target =
+            hir::AssignmentTarget::SimpleAssignmentTarget(self.hir.assignment_target_identifier(assign_ident));
+        let assign = self.hir.assignment_expression(span, hir::AssignmentOperator::Assign, assign_target, current);
+
+        let test_ident = self.hir.identifier_reference(span, tmp.clone());
+        let test_ident_expr = self.hir.identifier_reference_expression(test_ident);
+        let null = self.hir.literal_null_expression(self.hir.null_literal(span));
+        let is_nullish = self.hir.binary_expression(span, hir::BinaryOperator::Equality, test_ident_expr, null);
+
+        let mut test_parts = self.hir.new_vec_with_capacity(2);
+        test_parts.push(assign);
+        test_parts.push(is_nullish);
+        let test = self.hir.sequence_expression(span, test_parts);
+
+        let undefined = self.undefined_expression(span);
+        let access_ident = self.hir.identifier_reference(span, tmp);
+        let tmp_for_access = self.hir.identifier_reference_expression(access_ident);
+        let access = self.apply_chain_link(tmp_for_access, link);
+        let tail = self.build_chain_tail(access, links);
+        self.hir.conditional_expression(span, test, undefined, tail)
+    }
+
+    /// `o.m?.()` (and `o.m?.(...)` after further links): binds the receiver `o` to its own temp
+    /// and rebuilds `tmp.m` as the call's callee, so the method still runs with `this === tmp`
+    /// (i.e. `this === o`) instead of losing the receiver the way calling a bare temp would. A
+    /// computed key (`o[k]?.()`) gets its own temp too, since unlike a static/private name it can
+    /// be an arbitrary expression that must run exactly once. The member access itself (`tmp.m`
+    /// / `tmp[tmp_key]`) is rebuilt twice -- once for the nullish test, once as the callee -- so a
+    /// getter on `m` would run twice; that's the accepted tradeoff for keeping `this` correct
+    /// without a `Function.prototype.call` indirection.
+    fn build_optional_method_call(
+        &mut self,
+        span: Span,
+        member: hir::MemberExpression<'a>,
+        arguments: oxc_allocator::Vec<'a, hir::Argument<'a>>,
+        links: std::vec::Vec<ChainLink<'a>>,
+    ) -> hir::Expression<'a> {
+        let receiver_tmp = self.next_temp_name();
+        let (object, property, key_assign) = match member {
+            hir::MemberExpression::StaticMemberExpression(expr) => {
+                let hir::StaticMemberExpression { object, property, .. } = *expr;
+                (object, MethodProperty::Static(property.span, property.name), None)
+            }
+            hir::MemberExpression::PrivateFieldExpression(expr) => {
+                let hir::PrivateFieldExpression { object, field, .. } = *expr;
+                (object, MethodProperty::Private(field.span, field.name), None)
+            }
+            hir::MemberExpression::ComputedMemberExpression(expr) => {
+                let hir::ComputedMemberExpression { object, expression, .. } = *expr;
+                let key_tmp = self.next_temp_name();
+                let key_assign_ident = self.hir.identifier_reference(span, key_tmp.clone());
+                let key_assign_target = hir::AssignmentTarget::SimpleAssignmentTarget(
+                    self.hir.assignment_target_identifier(key_assign_ident),
+                );
+                let key_assign = self.hir.assignment_expression(
+                    span,
+                    hir::AssignmentOperator::Assign,
+                    key_assign_target,
+                    expression,
+                );
+                (object, MethodProperty::Computed(key_tmp), Some(key_assign))
+            }
+        };
+
+        let receiver_assign_ident = self.hir.identifier_reference(span, receiver_tmp.clone());
+        let receiver_assign_target = hir::AssignmentTarget::SimpleAssignmentTarget(
+            self.hir.assignment_target_identifier(receiver_assign_ident),
+        );
+        let receiver_assign =
+            self.hir.assignment_expression(span, hir::AssignmentOperator::Assign, receiver_assign_target, object);
+
+        let mut test_parts = self.hir.new_vec_with_capacity(3);
+        test_parts.push(receiver_assign);
+        if let Some(key_assign) = key_assign {
+            test_parts.push(key_assign);
+        }
+        let null = self.hir.literal_null_expression(self.hir.null_literal(span));
+        let member_for_test = self.rebuild_method_access(span, &receiver_tmp, &property);
+        let is_nullish = self.hir.binary_expression(span, hir::BinaryOperator::Equality, member_for_test, null);
+        test_parts.push(is_nullish);
+        let test = self.hir.sequence_expression(span, test_parts);
+
+        let undefined = self.undefined_expression(span);
+        let callee = self.rebuild_method_access(span, &receiver_tmp, &property);
+        let call = self.hir.call_expression(span, callee, arguments, false);
+        let tail = self.build_chain_tail(call, links);
+        self.hir.conditional_expression(span, test, undefined, tail)
+    }
+
+    /// Rebuilds `tmp.m` / `tmp#m` / `tmp[tmp_key]` from a captured [`MethodProperty`] shape and
+    /// the name of the temp holding the already-evaluated receiver.
+    fn rebuild_method_access(
+        &mut self,
+        span: Span,
+        receiver_tmp: &oxc_span::Atom<'a>,
+        property: &MethodProperty<'a>,
+    ) -> hir::Expression<'a> {
+        let receiver_ident = self.hir.identifier_reference(span, receiver_tmp.clone());
+        let receiver = self.hir.identifier_reference_expression(receiver_ident);
+        match property {
+            MethodProperty::Static(name_span, name) => {
+                let property = self.hir.identifier_name(*name_span, name.clone());
+                let member = self.hir.static_member_expression(span, receiver, property, false);
+                self.hir.member_expression(member)
+            }
+            MethodProperty::Private(name_span, name) => {
+                let field = self.hir.private_identifier(*name_span, name.clone());
+                let member = self.hir.private_field_expression(span, receiver, field, false);
+                self.hir.member_expression(member)
+            }
+            MethodProperty::Computed(key_tmp) => {
+                let key_ident = self.hir.identifier_reference(span, key_tmp.clone());
+                let key = self.hir.identifier_reference_expression(key_ident);
+                let member = self.hir.computed_member_expression(span, receiver, key, false);
+                self.hir.member_expression(member)
+            }
+        }
+    }
+
+    fn apply_chain_link(&mut self, object: hir::Expression<'a>, link: ChainLink<'a>) -> hir::Expression<'a> {
+        match link {
+            ChainLink::StaticMember { span, property, .. } => {
+                let member = self.hir.static_member_expression(span, object, property, false);
+                self.hir.member_expression(member)
+            }
+            ChainLink::ComputedMember { span, expression, .. } => {
+                let member = self.hir.computed_member_expression(span, object, expression, false);
+                self.hir.member_expression(member)
+            }
+            ChainLink::PrivateField { span, field, .. } => {
+                let member = self.hir.private_field_expression(span, object, field, false);
+                self.hir.member_expression(member)
+            }
+            ChainLink::Call { span, arguments, .. } => {
+                self.hir.call_expression(span, object, arguments, false)
+            }
+        }
+    }
+
+    /// `void 0`, the way most transpilers spell a guaranteed-unshadowed `undefined`.
+    fn undefined_expression(&mut self, span: Span) -> hir::Expression<'a> {
+        let zero = self.hir.number_literal(span, 0.0, "0", hir::NumberBase::Decimal);
+        let zero = self.hir.literal_number_expression(zero);
+        self.hir.unary_expression(span, hir::UnaryOperator::Void, true, zero)
+    }
+
+    /// Mints a fresh, source-unreachable binding name for a chain's temporary, and queues it to
+    /// be hoisted as a `var` declaration at the next enclosing var scope (see
+    /// [`crate::AstLower::prepend_hoisted_var_declaration`]) -- otherwise `(tmp = base)` would
+    /// assign an undeclared binding, a `ReferenceError` under strict mode.
+    fn next_temp_name(&mut self) -> oxc_span::Atom<'a> {
+        let id = self.next_temp_id;
+        self.next_temp_id += 1;
+        let name: oxc_span::Atom<'a> = format!("_oc{id}").into();
+        self.pending_chain_temps.push(name.clone());
+        name
+    }
+}
+
+fn member_object<'a, 'b>(member: &'b ast::MemberExpression<'a>) -> &'b ast::Expression<'a> {
+    match member {
+        ast::MemberExpression::ComputedMemberExpression(member) => &member.object,
+        ast::MemberExpression::StaticMemberExpression(member) => &member.object,
+        ast::MemberExpression::PrivateFieldExpression(member) => &member.object,
+    }
+}
+
+fn chain_link_span(link: &ChainLink) -> Span {
+    match link {
+        ChainLink::StaticMember { span, .. }
+        | ChainLink::ComputedMember { span, .. }
+        | ChainLink::PrivateField { span, .. }
+        | ChainLink::Call { span, .. } => *span,
+    }
+}
+
+fn chain_link_optional(link: &ChainLink) -> bool {
+    match link {
+        ChainLink::StaticMember { optional, .. }
+        | ChainLink::ComputedMember { optional, .. }
+        | ChainLink::PrivateField { optional, .. }
+        | ChainLink::Call { optional, .. } => *optional,
+    }
+}
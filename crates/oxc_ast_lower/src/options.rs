@@ -0,0 +1,56 @@
+/// Which JSX transform `lower_jsx_element`/`lower_jsx_fragment` desugar into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsxRuntime {
+    /// `pragma(type, props, ...children)`, e.g. `React.createElement("div", { id }, child)`.
+    Classic,
+    /// The automatic runtime: `jsx(type, props)` for a single (or no) child, `jsxs(type, props)`
+    /// once there's more than one, with `children` folded into `props` and `key` pulled out into
+    /// its own trailing argument instead.
+    Automatic,
+}
+
+/// Configuration for [`crate::AstLower`] that controls desugaring choices which don't change the
+/// meaning of the program but do change the shape of the emitted HIR.
+///
+/// All fields default to the most conservative, 1:1-with-source behavior.
+#[derive(Debug, Clone)]
+pub struct AstLowerOptions {
+    /// Which transform JSX lowers into.
+    pub jsx_runtime: JsxRuntime,
+    /// Classic runtime: callee used for a desugared JSX element, e.g. `"React.createElement"`.
+    pub jsx_pragma: String,
+    /// Classic runtime: callee used for a desugared JSX fragment, e.g. `"React.Fragment"`.
+    pub jsx_pragma_frag: String,
+    /// Automatic runtime: callee for an element with zero or one child, e.g. `"_jsx"`.
+    pub jsx_automatic_jsx: String,
+    /// Automatic runtime: callee for an element with more than one child, e.g. `"_jsxs"`.
+    pub jsx_automatic_jsxs: String,
+    /// Automatic runtime: the `type` value used for a fragment, e.g. `"_Fragment"`.
+    pub jsx_automatic_fragment: String,
+    /// When set, `lower_binary_expression`/`lower_unary_expression` evaluate constant
+    /// subexpressions as they lower them instead of keeping a 1:1 tree.
+    pub constant_fold: bool,
+    /// When set, `lower_chain_expression` rewrites an optional chain (`?.`) into explicit
+    /// `ConditionalExpression`/temporary-binding HIR with no `optional` flags left anywhere,
+    /// instead of the faithful 1:1 `ChainExpression` tree.
+    pub desugar_optional_chaining: bool,
+    /// When set, `AstLower` records a [`crate::cross_ref::CrossReference`] of every
+    /// definition/reference it produces, for external IDE/indexing tools. See [`crate::cross_ref`].
+    pub emit_cross_reference: bool,
+}
+
+impl Default for AstLowerOptions {
+    fn default() -> Self {
+        Self {
+            jsx_runtime: JsxRuntime::Classic,
+            jsx_pragma: "React.createElement".to_string(),
+            jsx_pragma_frag: "React.Fragment".to_string(),
+            jsx_automatic_jsx: "_jsx".to_string(),
+            jsx_automatic_jsxs: "_jsxs".to_string(),
+            jsx_automatic_fragment: "_Fragment".to_string(),
+            constant_fold: false,
+            desugar_optional_chaining: false,
+            emit_cross_reference: false,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! An owning transform over `hir` trees, the counterpart to [`crate::visit::Visitor`].
+//!
+//! A `Reconstructor` implementation overrides `reconstruct_*` for the node kinds it wants to
+//! rewrite. The defaults rebuild expression and block statements and binary, logical,
+//! conditional, and array expressions from their (possibly rewritten) children via the same
+//! [`HirBuilder`] `AstLower` itself uses. Every other statement or expression kind (and an
+//! array's spread/elision elements) is returned unchanged, without its children being visited,
+//! so a rewrite nested inside one of those kinds is not reached by the defaults.
+
+use oxc_allocator::Vec;
+use oxc_hir::{hir, hir_builder::HirBuilder};
+
+pub trait Reconstructor<'a>: Sized {
+    fn builder(&mut self) -> &mut HirBuilder<'a>;
+
+    fn reconstruct_program(&mut self, program: hir::Program<'a>) -> hir::Program<'a> {
+        walk_program(self, program)
+    }
+
+    fn reconstruct_statements(
+        &mut self,
+        stmts: Vec<'a, hir::Statement<'a>>,
+    ) -> Vec<'a, hir::Statement<'a>> {
+        walk_statements(self, stmts)
+    }
+
+    fn reconstruct_statement(&mut self, stmt: hir::Statement<'a>) -> hir::Statement<'a> {
+        walk_statement(self, stmt)
+    }
+
+    fn reconstruct_expression(&mut self, expr: hir::Expression<'a>) -> hir::Expression<'a> {
+        walk_expression(self, expr)
+    }
+
+    fn reconstruct_binary_expression(
+        &mut self,
+        span: oxc_span::Span,
+        left: hir::Expression<'a>,
+        operator: hir::BinaryOperator,
+        right: hir::Expression<'a>,
+    ) -> hir::Expression<'a> {
+        let left = self.reconstruct_expression(left);
+        let right = self.reconstruct_expression(right);
+        self.builder().binary_expression(span, left, operator, right)
+    }
+
+    fn reconstruct_logical_expression(
+        &mut self,
+        span: oxc_span::Span,
+        left: hir::Expression<'a>,
+        operator: hir::LogicalOperator,
+        right: hir::Expression<'a>,
+    ) -> hir::Expression<'a> {
+        let left = self.reconstruct_expression(left);
+        let right = self.reconstruct_expression(right);
+        self.builder().logical_expression(span, left, operator, right)
+    }
+
+    fn reconstruct_conditional_expression(
+        &mut self,
+        span: oxc_span::Span,
+        test: hir::Expression<'a>,
+        consequent: hir::Expression<'a>,
+        alternate: hir::Expression<'a>,
+    ) -> hir::Expression<'a> {
+        let test = self.reconstruct_expression(test);
+        let consequent = self.reconstruct_expression(consequent);
+        let alternate = self.reconstruct_expression(alternate);
+        self.builder().conditional_expression(span, test, consequent, alternate)
+    }
+
+    fn reconstruct_array_expression(
+        &mut self,
+        span: oxc_span::Span,
+        elements: Vec<'a, hir::ArrayExpressionElement<'a>>,
+        trailing_comma: Option<oxc_span::Span>,
+    ) -> hir::Expression<'a> {
+        let mut rebuilt = self.builder().new_vec_with_capacity(elements.len());
+        for element in elements {
+            let element = match element {
+                hir::ArrayExpressionElement::Expression(expr) => {
+                    hir::ArrayExpressionElement::Expression(self.reconstruct_expression(expr))
+                }
+                other => other,
+            };
+            rebuilt.push(element);
+        }
+        self.builder().array_expression(span, rebuilt, trailing_comma)
+    }
+}
+
+pub fn walk_program<'a, R: Reconstructor<'a>>(
+    reconstructor: &mut R,
+    program: hir::Program<'a>,
+) -> hir::Program<'a> {
+    let hir::Program { span, directives, body, .. } = program;
+    let body = reconstructor.reconstruct_statements(body);
+    reconstructor.builder().program(span, directives, body)
+}
+
+pub fn walk_statements<'a, R: Reconstructor<'a>>(
+    reconstructor: &mut R,
+    stmts: Vec<'a, hir::Statement<'a>>,
+) -> Vec<'a, hir::Statement<'a>> {
+    let mut rebuilt = reconstructor.builder().new_vec_with_capacity(stmts.len());
+    for stmt in stmts {
+        rebuilt.push(reconstructor.reconstruct_statement(stmt));
+    }
+    rebuilt
+}
+
+pub fn walk_statement<'a, R: Reconstructor<'a>>(
+    reconstructor: &mut R,
+    stmt: hir::Statement<'a>,
+) -> hir::Statement<'a> {
+    match stmt {
+        hir::Statement::ExpressionStatement(stmt) => {
+            let span = stmt.span;
+            let expression = reconstructor.reconstruct_expression(stmt.expression);
+            reconstructor.builder().expression_statement(span, expression)
+        }
+        hir::Statement::BlockStatement(stmt) => {
+            let span = stmt.span;
+            let body = reconstructor.reconstruct_statements(stmt.body);
+            reconstructor.builder().block_statement(span, body)
+        }
+        // Every other statement kind is passed through unchanged; a pass that cares about one
+        // of these overrides `reconstruct_statement` and falls back to `walk_statement` for the
+        // rest.
+        other => other,
+    }
+}
+
+pub fn walk_expression<'a, R: Reconstructor<'a>>(
+    reconstructor: &mut R,
+    expr: hir::Expression<'a>,
+) -> hir::Expression<'a> {
+    match expr {
+        hir::Expression::BinaryExpression(expr) => {
+            let hir::BinaryExpression { span, left, operator, right, .. } = *expr;
+            reconstructor.reconstruct_binary_expression(span, left, operator, right)
+        }
+        hir::Expression::LogicalExpression(expr) => {
+            let hir::LogicalExpression { span, left, operator, right, .. } = *expr;
+            reconstructor.reconstruct_logical_expression(span, left, operator, right)
+        }
+        hir::Expression::ConditionalExpression(expr) => {
+            let hir::ConditionalExpression { span, test, consequent, alternate, .. } = *expr;
+            reconstructor.reconstruct_conditional_expression(span, test, consequent, alternate)
+        }
+        hir::Expression::ArrayExpression(expr) => {
+            let hir::ArrayExpression { span, elements, trailing_comma, .. } = *expr;
+            reconstructor.reconstruct_array_expression(span, elements, trailing_comma)
+        }
+        other => other,
+    }
+}
@@ -0,0 +1,91 @@
+//! Concise `fn(T, U)`-shaped signature strings for `hir::Function`/`hir::MethodDefinition`,
+//! the way rust-analyzer's completion detail upgrades a `-> T` return-type hint to the full
+//! parameter list. Built on top of [`crate::display`]'s parameter-rendering so the signature a
+//! consumer gets from `signature_string()` always matches what [`crate::display::HirDisplay`]
+//! would print for the same node's parameter list.
+//!
+//! Exposed as an extension trait (`Function`/`MethodDefinition` are `oxc_hir` types, not ours)
+//! rather than a free function, so a caller reads it the way the originating completion feature
+//! requested: `func.signature_string()`.
+
+use std::fmt::Write as _;
+
+use oxc_hir::hir;
+
+use crate::display::{write_formal_parameters, HirFormatter};
+
+pub trait SignatureString {
+    /// A short one-line signature: name, qualifiers, and parameter shape, with no return type or
+    /// body -- e.g. `async f(a, { b }, ...rest)`, `get x()`, `constructor(a)`.
+    fn signature_string(&self) -> String;
+}
+
+impl<'a> SignatureString for hir::Function<'a> {
+    fn signature_string(&self) -> String {
+        let mut out = String::new();
+        if self.r#async {
+            out.push_str("async ");
+        }
+        if self.generator {
+            out.push('*');
+        }
+        if let Some(id) = &self.id {
+            let _ = write!(out, "{}", id.name);
+        }
+        write_signature_params(&mut out, &self.params);
+        out
+    }
+}
+
+impl<'a> SignatureString for hir::MethodDefinition<'a> {
+    fn signature_string(&self) -> String {
+        let mut out = String::new();
+        if self.r#static {
+            out.push_str("static ");
+        }
+        match self.kind {
+            hir::MethodDefinitionKind::Constructor => out.push_str("constructor"),
+            hir::MethodDefinitionKind::Get => out.push_str("get "),
+            hir::MethodDefinitionKind::Set => out.push_str("set "),
+            hir::MethodDefinitionKind::Method => {
+                if self.value.r#async {
+                    out.push_str("async ");
+                }
+                if self.value.generator {
+                    out.push('*');
+                }
+            }
+        }
+        if !matches!(self.kind, hir::MethodDefinitionKind::Constructor) {
+            write_property_key(&mut out, &self.key);
+        }
+        write_signature_params(&mut out, &self.value.params);
+        out
+    }
+}
+
+/// Renders `(a, { b }, ...rest)` into `out` by reusing [`crate::display`]'s
+/// `write_formal_parameters`, which already knows how to flatten destructured parameters down to
+/// their bound names.
+fn write_signature_params(out: &mut String, params: &hir::FormalParameters) {
+    out.push('(');
+    let mut sink = String::new();
+    // `write_formal_parameters` writes through a `HirFormatter`; `signature_only` doesn't matter
+    // here since parameter patterns have no body to omit, but a formatter is still required to
+    // reuse the shared renderer rather than duplicating it.
+    let mut formatter = HirFormatter::for_signature_string(&mut sink);
+    let _ = write_formal_parameters(&mut formatter, params);
+    out.push_str(&sink);
+    out.push(')');
+}
+
+fn write_property_key(out: &mut String, key: &hir::PropertyKey) {
+    match key {
+        hir::PropertyKey::Identifier(ident) => out.push_str(&ident.name),
+        hir::PropertyKey::PrivateIdentifier(ident) => {
+            out.push('#');
+            out.push_str(&ident.name);
+        }
+        hir::PropertyKey::Expression(_) => out.push_str("[computed]"),
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use oxc_span::Span;
+
+/// Stable identity for a HIR node, minted once per node as `AstLower` walks the tree.
+///
+/// Ids are handed out in traversal order starting at zero and are never reused, so they can be
+/// used as a dense key for side tables that need to attach facts to a node after the fact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HirId(u32);
+
+impl HirId {
+    pub(crate) fn from_u32(id: u32) -> Self {
+        Self(id)
+    }
+
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+/// Why a node exists: did the user write it, or did a desugaring pass synthesize it?
+///
+/// Mirrors rustc's `DesugaringKind`/`DesugaringLoc`: later passes (diagnostics, source maps, a
+/// minifier) can check this before pointing a user at a span or treating a node as literal
+/// source, instead of being misled by compiler-generated code that merely carries a real-looking
+/// span. New desugarings (for-of, async/await, logical assignment, ...) extend this enum rather
+/// than inventing a parallel ad-hoc flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DesugaringKind {
+    #[default]
+    UserWritten,
+    OptionalChaining,
+    Jsx,
+    LogicalAssignment,
+    TsEnum,
+}
+
+/// The coarse-grained category of a node recorded in a [`HirSourceMap`].
+///
+/// This is deliberately not a full owned copy of the `hir` node (most `hir` node types aren't
+/// `Clone`, and duplicating the tree defeats the point of an arena); it's enough for a consumer
+/// to know what it's looking at before deciding whether to dig into the `hir` tree itself via the
+/// node's `ast_span`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HirNodeKind {
+    Statement,
+    Expression,
+    Pattern,
+    Property,
+    Identifier,
+    ModuleDeclaration,
+    /// The IIFE call statement that populates a `const enum`'s bindings (see
+    /// [`crate::ts_enum`]). Distinct from a plain [`Self::Statement`] so a later pass can find
+    /// the member-value assignments it needs to inline member accesses without re-deriving which
+    /// statements came from a `const enum` in the first place.
+    ConstEnumBinding,
+    /// An import or export specifier (`{ a as b }`, a default specifier, a namespace specifier).
+    Specifier,
+    /// A class member: a method, a property, an accessor, or a static block.
+    ClassElement,
+    /// A function or method body.
+    FunctionBody,
+    /// One `name = init` clause of a `var`/`let`/`const`.
+    VariableDeclarator,
+    /// A `@decorator` application.
+    Decorator,
+}
+
+/// A side table linking lowered `hir` nodes back to the `ast` node they were lowered from.
+///
+/// Lookups go both ways: given a `HirId` for a diagnostic or fold result produced deep in a
+/// later pass, `ast_span` recovers the original source location to point the user at; given an
+/// `ast` span (e.g. from a cursor position), `hir_ids_at` recovers the node(s) lowering produced
+/// for it. `parent` additionally recovers the nesting parent recorded for a node at the moment
+/// `AstLower` lowered it, turning the map into a lightweight node/parent index over the lowered
+/// tree without requiring `hir` nodes to carry parent pointers themselves.
+#[derive(Debug, Default)]
+pub struct HirSourceMap {
+    ast_spans: Vec<Span>,
+    by_ast_span: HashMap<Span, Vec<HirId>>,
+    kinds: Vec<HirNodeKind>,
+    parents: Vec<Option<HirId>>,
+    desugarings: Vec<DesugaringKind>,
+}
+
+impl HirSourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        kind: HirNodeKind,
+        ast_span: Span,
+        parent: Option<HirId>,
+        desugaring: DesugaringKind,
+    ) -> HirId {
+        let id = HirId::from_u32(u32::try_from(self.ast_spans.len()).expect("too many HIR nodes"));
+        self.ast_spans.push(ast_span);
+        self.by_ast_span.entry(ast_span).or_default().push(id);
+        self.kinds.push(kind);
+        self.parents.push(parent);
+        self.desugarings.push(desugaring);
+        id
+    }
+
+    /// Records that `alias_span` (e.g. a wrapper span like `(expr)` or `expr as T` dropped during
+    /// unwrapping) should also resolve to the HIR node already recorded under `id`.
+    pub(crate) fn alias(&mut self, id: HirId, alias_span: Span) {
+        self.by_ast_span.entry(alias_span).or_default().push(id);
+    }
+
+    #[must_use]
+    pub fn ast_span(&self, id: HirId) -> Option<Span> {
+        self.ast_spans.get(id.index() as usize).copied()
+    }
+
+    #[must_use]
+    pub fn kind(&self, id: HirId) -> Option<HirNodeKind> {
+        self.kinds.get(id.index() as usize).copied()
+    }
+
+    /// The nesting parent recorded for `id`, i.e. the id of the node that was being lowered when
+    /// `id` was minted. `None` for a top-level node (the program itself has no id) or for a node
+    /// lowered outside of any other tracked node's traversal.
+    #[must_use]
+    pub fn parent(&self, id: HirId) -> Option<HirId> {
+        self.parents.get(id.index() as usize).copied().flatten()
+    }
+
+    /// Why `id` exists: [`DesugaringKind::UserWritten`] unless it was synthesized by a desugaring
+    /// pass (optional chaining, JSX, ...) that was active when `AstLower` recorded it.
+    #[must_use]
+    pub fn desugaring(&self, id: HirId) -> Option<DesugaringKind> {
+        self.desugarings.get(id.index() as usize).copied()
+    }
+
+    #[must_use]
+    pub fn hir_ids_at(&self, ast_span: Span) -> &[HirId] {
+        self.by_ast_span.get(&ast_span).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ast_spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ast_spans.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DesugaringKind, HirNodeKind, HirSourceMap};
+    use oxc_span::Span;
+
+    #[test]
+    fn alias_resolves_to_the_aliased_node_not_the_most_recent_one() {
+        let mut map = HirSourceMap::new();
+        let inner_span = Span::new(4, 7);
+        let wrapper_span = Span::new(0, 8);
+        let inner_id = map.insert(HirNodeKind::Expression, inner_span, None, DesugaringKind::UserWritten);
+        // A descendant minted after `inner_id` (e.g. a trailing leaf of the inner expression)
+        // must not be what a wrapper span like `(expr)` resolves to.
+        let descendant_id =
+            map.insert(HirNodeKind::Expression, Span::new(5, 6), Some(inner_id), DesugaringKind::UserWritten);
+        assert_ne!(inner_id, descendant_id);
+
+        map.alias(inner_id, wrapper_span);
+
+        assert_eq!(map.hir_ids_at(wrapper_span), &[inner_id]);
+        assert_eq!(map.hir_ids_at(inner_span), &[inner_id]);
+    }
+
+    #[test]
+    fn ids_are_minted_in_insertion_order_starting_at_zero() {
+        let mut map = HirSourceMap::new();
+        let first = map.insert(HirNodeKind::Statement, Span::new(0, 1), None, DesugaringKind::UserWritten);
+        let second = map.insert(HirNodeKind::Statement, Span::new(1, 2), None, DesugaringKind::UserWritten);
+        assert_eq!(first.index(), 0);
+        assert_eq!(second.index(), 1);
+    }
+}
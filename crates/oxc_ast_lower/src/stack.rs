@@ -0,0 +1,20 @@
+//! Guards the hot recursive entry points of lowering against blowing the native stack on
+//! adversarial or machine-generated input (deeply nested binary chains, member accesses,
+//! array/object literals, ...), the same way rustc wraps `lower_expr_mut`.
+//!
+//! Rather than impose an arbitrary nesting cap that would reject otherwise-valid programs, we
+//! check remaining stack before recursing and grow onto a fresh segment when we're within the red
+//! zone of running out.
+
+/// Bytes of headroom that must remain before a guarded call; below this we grow the stack rather
+/// than risk overflowing partway through the call.
+const RED_ZONE_BYTES: usize = 128 * 1024;
+
+/// Size of each freshly-allocated segment once we do need to grow.
+const STACK_SEGMENT_BYTES: usize = 1024 * 1024;
+
+/// Runs `f` with at least [`RED_ZONE_BYTES`] of stack guaranteed, growing onto a new
+/// [`STACK_SEGMENT_BYTES`]-sized segment first if necessary.
+pub(crate) fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE_BYTES, STACK_SEGMENT_BYTES, f)
+}
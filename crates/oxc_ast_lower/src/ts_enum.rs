@@ -0,0 +1,218 @@
+//! Down-levels a TypeScript `enum` declaration into the runtime HIR a `tsc`-equivalent transform
+//! emits, rather than dropping it: a `var` binding for the enum's name, followed by an IIFE that
+//! populates it with both the forward (`E.A`) and reverse (`E[0]`) mappings.
+//!
+//! `enum E { A, B = 2, C }` becomes:
+//! ```text
+//! var E;
+//! (function (E) {
+//!     E[E["A"] = 0] = "A";
+//!     E[E["B"] = 2] = "B";
+//!     E[E["C"] = 3] = "C";
+//! })(E || (E = {}));
+//! ```
+//! Each member's value is either its own initializer, when it constant-folds to a number literal,
+//! or one more than the previous member's numeric value. A string initializer only gets the
+//! forward assignment (there's no sensible reverse mapping from a string back to itself sharing
+//! the same property), and once a member's value can't be proven to be a numeric constant, a later
+//! un-initialized member has no defined value to continue from. `tsc` makes that a checker error
+//! ("Enum member must have initializer"); without a checker pass here we emit an explicit `void 0`
+//! placeholder instead of silently fabricating a wrong number.
+//!
+//! Of the synthesized nodes, only the two top-level statements get a [`crate::HirId`] of their
+//! own (tagged [`crate::DesugaringKind::TsEnum`] by the caller); the function/assignment
+//! scaffolding in between is untracked synthetic plumbing, the same granularity
+//! [`crate::optional_chain`] uses for its desugaring. The enum's name binding and each member's
+//! initializer expression are lowered from source and get ids like any other source node.
+
+use oxc_allocator::Box;
+use oxc_ast::ast;
+use oxc_hir::hir;
+use oxc_span::{Atom, Span};
+
+use crate::AstLower;
+
+/// Either branch keeps the already-lowered initializer/counter value alongside which assignment
+/// shape it needs.
+enum MemberValue<'a> {
+    /// `E[E["A"] = value] = "A"` -- value is numeric (or at least not provably a string).
+    Reverse(hir::Expression<'a>),
+    /// `E["A"] = value` -- value is a string, so there's no reverse mapping to add.
+    ForwardOnly(hir::Expression<'a>),
+}
+
+impl<'a> AstLower<'a> {
+    /// The `var E;` half of the down-leveled enum. Also valid as the inner declaration of an
+    /// `export` wrapper, since that's the only other position a `TSEnumDeclaration` can appear in.
+    pub(crate) fn lower_ts_enum_variable_declaration(
+        &mut self,
+        decl: &ast::TSEnumDeclaration<'a>,
+    ) -> Box<'a, hir::VariableDeclaration<'a>> {
+        let span = self.lower_span(decl.span);
+        let binding = self.lower_binding_identifier(&decl.id);
+        let pattern = self.hir.binding_identifier_pattern(binding);
+        let declarator =
+            self.hir.variable_declarator(span, hir::VariableDeclarationKind::Var, pattern, None, false);
+        let mut declarators = self.hir.new_vec_with_capacity(1);
+        declarators.push(declarator);
+        self.hir.variable_declaration(span, hir::VariableDeclarationKind::Var, declarators)
+    }
+
+    /// The `(function (E) { ... })(E || (E = {}))` half that actually populates the bindings
+    /// declared by [`Self::lower_ts_enum_variable_declaration`].
+    pub(crate) fn lower_ts_enum_initializer_call(
+        &mut self,
+        decl: &ast::TSEnumDeclaration<'a>,
+    ) -> hir::Expression<'a> {
+        let span = self.lower_span(decl.span);
+        let name = self.lower_ident(decl.id.name.clone());
+
+        let mut counter = Some(0.0_f64);
+        let mut body = self.hir.new_vec_with_capacity(decl.members.len());
+        for member in &decl.members {
+            body.push(self.lower_ts_enum_member(member, &name, &mut counter));
+        }
+
+        let param_ident = self.hir.binding_identifier(span, name.clone());
+        let param_pattern = self.hir.binding_identifier_pattern(param_ident);
+        let empty_decorators = self.hir.new_vec_with_capacity(0);
+        let param = self.hir.formal_parameter(span, param_pattern, empty_decorators);
+        let mut params_items = self.hir.new_vec_with_capacity(1);
+        params_items.push(param);
+        let params = self.hir.formal_parameters(span, hir::FormalParameterKind::FormalParameter, params_items);
+        let empty_directives = self.hir.new_vec_with_capacity(0);
+        let function_body = self.hir.function_body(span, empty_directives, body);
+        let func = self.hir.function(
+            hir::FunctionType::FunctionExpression,
+            span,
+            None,
+            false,
+            false,
+            false,
+            params,
+            Some(function_body),
+        );
+        let callee = self.hir.function_expression(func);
+
+        // `E || (E = {})`: reuse the existing object if a previous merged declaration (or this
+        // same IIFE on a prior evaluation, e.g. if the module re-runs) already created one.
+        let left = self.enum_name_ref_expression(span, &name);
+        let assign_ident = self.hir.identifier_reference(span, name.clone());
+        let assign_target =
+            hir::AssignmentTarget::SimpleAssignmentTarget(self.hir.assignment_target_identifier(assign_ident));
+        let empty_properties = self.hir.new_vec_with_capacity(0);
+        let empty_object = self.hir.object_expression(span, empty_properties, false);
+        let assign =
+            self.hir.assignment_expression(span, hir::AssignmentOperator::Assign, assign_target, empty_object);
+        let init_arg = self.hir.logical_expression(span, left, hir::LogicalOperator::Or, assign);
+
+        let mut arguments = self.hir.new_vec_with_capacity(1);
+        arguments.push(hir::Argument::Expression(init_arg));
+        self.hir.call_expression(span, callee, arguments, false)
+    }
+
+    fn lower_ts_enum_member(
+        &mut self,
+        member: &ast::TSEnumMember<'a>,
+        enum_name: &Atom<'a>,
+        counter: &mut Option<f64>,
+    ) -> hir::Statement<'a> {
+        let span = self.lower_span(member.span);
+        let member_name = self.enum_member_name(&member.id);
+
+        let value = match &member.initializer {
+            Some(init) => {
+                let lowered = self.lower_expression(init);
+                match &lowered {
+                    hir::Expression::NumberLiteral(lit) => {
+                        *counter = Some(lit.value + 1.0);
+                        MemberValue::Reverse(lowered)
+                    }
+                    hir::Expression::StringLiteral(_) => {
+                        *counter = None;
+                        MemberValue::ForwardOnly(lowered)
+                    }
+                    // Not statically known to be a number or a string (e.g. a computed
+                    // expression): `tsc` still emits the reverse mapping optimistically, but can
+                    // no longer tell us what an un-initialized member after this one is worth.
+                    _ => {
+                        *counter = None;
+                        MemberValue::Reverse(lowered)
+                    }
+                }
+            }
+            None => match *counter {
+                Some(next) => {
+                    *counter = Some(next + 1.0);
+                    MemberValue::Reverse(self.number_literal_expression(span, next))
+                }
+                None => MemberValue::Reverse(self.ts_enum_opaque_value(span)),
+            },
+        };
+
+        match value {
+            MemberValue::ForwardOnly(value_expr) => {
+                let target = self.enum_member_assignment_target(span, enum_name, &member_name);
+                let assign = self.hir.assignment_expression(span, hir::AssignmentOperator::Assign, target, value_expr);
+                self.hir.expression_statement(span, assign)
+            }
+            MemberValue::Reverse(value_expr) => {
+                let inner_target = self.enum_member_assignment_target(span, enum_name, &member_name);
+                let inner_assign =
+                    self.hir.assignment_expression(span, hir::AssignmentOperator::Assign, inner_target, value_expr);
+                let outer_object = self.enum_name_ref_expression(span, enum_name);
+                let outer_member = self.hir.computed_member_expression(span, outer_object, inner_assign, false);
+                let outer_target =
+                    hir::AssignmentTarget::SimpleAssignmentTarget(self.hir.member_assignment_target(outer_member));
+                let member_name_str = self.string_literal_expression(span, member_name.clone());
+                let outer_assign =
+                    self.hir.assignment_expression(span, hir::AssignmentOperator::Assign, outer_target, member_name_str);
+                self.hir.expression_statement(span, outer_assign)
+            }
+        }
+    }
+
+    fn enum_member_name(&mut self, name: &ast::TSEnumMemberName<'a>) -> Atom<'a> {
+        match name {
+            ast::TSEnumMemberName::Identifier(ident) => self.lower_ident(ident.name.clone()),
+            ast::TSEnumMemberName::StringLiteral(lit) => self.lower_ident(lit.value.clone()),
+        }
+    }
+
+    /// `E["name"]` as an assignment target.
+    fn enum_member_assignment_target(
+        &mut self,
+        span: Span,
+        enum_name: &Atom<'a>,
+        member_name: &Atom<'a>,
+    ) -> hir::AssignmentTarget<'a> {
+        let object = self.enum_name_ref_expression(span, enum_name);
+        let key = self.string_literal_expression(span, member_name.clone());
+        let member = self.hir.computed_member_expression(span, object, key, false);
+        hir::AssignmentTarget::SimpleAssignmentTarget(self.hir.member_assignment_target(member))
+    }
+
+    fn enum_name_ref_expression(&mut self, span: Span, name: &Atom<'a>) -> hir::Expression<'a> {
+        let ident = self.hir.identifier_reference(span, name.clone());
+        self.hir.identifier_reference_expression(ident)
+    }
+
+    fn string_literal_expression(&mut self, span: Span, value: Atom<'a>) -> hir::Expression<'a> {
+        let lit = self.hir.string_literal(span, value);
+        self.hir.literal_string_expression(lit)
+    }
+
+    fn number_literal_expression(&mut self, span: Span, value: f64) -> hir::Expression<'a> {
+        let raw = self.hir.alloc_str(&value.to_string());
+        let lit = self.hir.number_literal(span, value, raw, hir::NumberBase::Decimal);
+        self.hir.literal_number_expression(lit)
+    }
+
+    /// Placeholder for a member whose auto-increment value isn't well-defined. See the module
+    /// doc comment: `tsc` would refuse to compile this, we emit `void 0` instead of a number that
+    /// would quietly be wrong.
+    fn ts_enum_opaque_value(&mut self, span: Span) -> hir::Expression<'a> {
+        let zero = self.number_literal_expression(span, 0.0);
+        self.hir.unary_expression(span, hir::UnaryOperator::Void, true, zero)
+    }
+}
@@ -0,0 +1,401 @@
+//! A read-only, recursive walker over `hir` trees.
+//!
+//! `AstLower` hand-writes one recursive traversal over the `ast`; `Visitor` factors the shape of
+//! *that same traversal* out so later passes over the already-lowered `hir` (scope analysis,
+//! linting, a minifier) don't each have to re-implement the full match. `visit_statement` and
+//! `visit_expression` default to the matching `walk_*` free function, and the node kinds with a
+//! `visit_*` method of their own default to visiting that node's children. Override just the
+//! node(s) you care about; the defaults keep recursing into everything else they reach. The
+//! defaults do not descend into declarations, module declarations, `for-in`/`for-of`
+//! statements, a `for` statement's init, an assignment's target, spread call arguments or array
+//! elements, template literals, tagged templates, `import()`, `#x in obj`, or functions, arrow
+//! functions, and classes.
+
+use oxc_allocator::Vec;
+use oxc_hir::hir;
+
+pub trait Visitor<'a>: Sized {
+    fn visit_program(&mut self, program: &hir::Program<'a>) {
+        walk_program(self, program);
+    }
+
+    fn visit_statements(&mut self, stmts: &Vec<'a, hir::Statement<'a>>) {
+        walk_statements(self, stmts);
+    }
+
+    fn visit_statement(&mut self, stmt: &hir::Statement<'a>) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_block_statement(&mut self, stmts: &Vec<'a, hir::Statement<'a>>) {
+        self.visit_statements(stmts);
+    }
+
+    fn visit_if_statement(
+        &mut self,
+        test: &hir::Expression<'a>,
+        consequent: &hir::Statement<'a>,
+        alternate: Option<&hir::Statement<'a>>,
+    ) {
+        self.visit_expression(test);
+        self.visit_statement(consequent);
+        if let Some(alternate) = alternate {
+            self.visit_statement(alternate);
+        }
+    }
+
+    fn visit_while_statement(&mut self, test: &hir::Expression<'a>, body: &hir::Statement<'a>) {
+        self.visit_expression(test);
+        self.visit_statement(body);
+    }
+
+    fn visit_for_statement(
+        &mut self,
+        test: Option<&hir::Expression<'a>>,
+        update: Option<&hir::Expression<'a>>,
+        body: &hir::Statement<'a>,
+    ) {
+        if let Some(test) = test {
+            self.visit_expression(test);
+        }
+        if let Some(update) = update {
+            self.visit_expression(update);
+        }
+        self.visit_statement(body);
+    }
+
+    fn visit_return_statement(&mut self, argument: Option<&hir::Expression<'a>>) {
+        if let Some(argument) = argument {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_expression_statement(&mut self, expression: &hir::Expression<'a>) {
+        self.visit_expression(expression);
+    }
+
+    fn visit_expression(&mut self, expr: &hir::Expression<'a>) {
+        walk_expression(self, expr);
+    }
+
+    fn visit_binary_expression(
+        &mut self,
+        left: &hir::Expression<'a>,
+        _operator: hir::BinaryOperator,
+        right: &hir::Expression<'a>,
+    ) {
+        self.visit_expression(left);
+        self.visit_expression(right);
+    }
+
+    fn visit_logical_expression(
+        &mut self,
+        left: &hir::Expression<'a>,
+        _operator: hir::LogicalOperator,
+        right: &hir::Expression<'a>,
+    ) {
+        self.visit_expression(left);
+        self.visit_expression(right);
+    }
+
+    fn visit_unary_expression(&mut self, argument: &hir::Expression<'a>) {
+        self.visit_expression(argument);
+    }
+
+    fn visit_assignment_expression(&mut self, right: &hir::Expression<'a>) {
+        self.visit_expression(right);
+    }
+
+    fn visit_conditional_expression(
+        &mut self,
+        test: &hir::Expression<'a>,
+        consequent: &hir::Expression<'a>,
+        alternate: &hir::Expression<'a>,
+    ) {
+        self.visit_expression(test);
+        self.visit_expression(consequent);
+        self.visit_expression(alternate);
+    }
+
+    fn visit_call_expression(
+        &mut self,
+        callee: &hir::Expression<'a>,
+        arguments: &Vec<'a, hir::Argument<'a>>,
+    ) {
+        self.visit_expression(callee);
+        for argument in arguments {
+            if let hir::Argument::Expression(expr) = argument {
+                self.visit_expression(expr);
+            }
+        }
+    }
+
+    fn visit_member_expression(&mut self, object: &hir::Expression<'a>) {
+        self.visit_expression(object);
+    }
+
+    fn visit_array_expression(&mut self, elements: &Vec<'a, hir::ArrayExpressionElement<'a>>) {
+        for element in elements {
+            if let hir::ArrayExpressionElement::Expression(expr) = element {
+                self.visit_expression(expr);
+            }
+        }
+    }
+
+    fn visit_object_expression(&mut self, properties: &Vec<'a, hir::ObjectProperty<'a>>) {
+        for property in properties {
+            match property {
+                hir::ObjectProperty::Property(property) => {
+                    if let hir::PropertyKey::Expression(key) = &property.key {
+                        self.visit_expression(key);
+                    }
+                    if let hir::PropertyValue::Expression(value) = &property.value {
+                        self.visit_expression(value);
+                    }
+                }
+                hir::ObjectProperty::SpreadProperty(spread) => {
+                    self.visit_expression(&spread.argument);
+                }
+            }
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, _ident: &hir::IdentifierReference) {}
+}
+
+pub fn walk_program<'a, V: Visitor<'a>>(visitor: &mut V, program: &hir::Program<'a>) {
+    visitor.visit_statements(&program.body);
+}
+
+pub fn walk_statements<'a, V: Visitor<'a>>(visitor: &mut V, stmts: &Vec<'a, hir::Statement<'a>>) {
+    for stmt in stmts {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_statement<'a, V: Visitor<'a>>(visitor: &mut V, stmt: &hir::Statement<'a>) {
+    match stmt {
+        hir::Statement::BlockStatement(block) => visitor.visit_block_statement(&block.body),
+        hir::Statement::IfStatement(stmt) => visitor.visit_if_statement(
+            &stmt.test,
+            &stmt.consequent,
+            stmt.alternate.as_ref(),
+        ),
+        hir::Statement::WhileStatement(stmt) => {
+            visitor.visit_while_statement(&stmt.test, &stmt.body);
+        }
+        hir::Statement::DoWhileStatement(stmt) => {
+            visitor.visit_statement(&stmt.body);
+            visitor.visit_expression(&stmt.test);
+        }
+        hir::Statement::ForStatement(stmt) => visitor.visit_for_statement(
+            stmt.test.as_ref(),
+            stmt.update.as_ref(),
+            &stmt.body,
+        ),
+        hir::Statement::ReturnStatement(stmt) => {
+            visitor.visit_return_statement(stmt.argument.as_ref());
+        }
+        hir::Statement::ExpressionStatement(stmt) => {
+            visitor.visit_expression_statement(&stmt.expression);
+        }
+        hir::Statement::LabeledStatement(stmt) => visitor.visit_statement(&stmt.body),
+        hir::Statement::ThrowStatement(stmt) => visitor.visit_expression(&stmt.argument),
+        hir::Statement::TryStatement(stmt) => {
+            visitor.visit_block_statement(&stmt.block.body);
+            if let Some(handler) = &stmt.handler {
+                visitor.visit_block_statement(&handler.body.body);
+            }
+            if let Some(finalizer) = &stmt.finalizer {
+                visitor.visit_block_statement(&finalizer.body);
+            }
+        }
+        hir::Statement::SwitchStatement(stmt) => {
+            visitor.visit_expression(&stmt.discriminant);
+            for case in &stmt.cases {
+                if let Some(test) = &case.test {
+                    visitor.visit_expression(test);
+                }
+                visitor.visit_statements(&case.consequent);
+            }
+        }
+        hir::Statement::WithStatement(stmt) => {
+            visitor.visit_expression(&stmt.object);
+            visitor.visit_statement(&stmt.body);
+        }
+        hir::Statement::BreakStatement(_)
+        | hir::Statement::ContinueStatement(_)
+        | hir::Statement::DebuggerStatement(_)
+        | hir::Statement::EmptyStatement(_)
+        | hir::Statement::ForInStatement(_)
+        | hir::Statement::ForOfStatement(_)
+        | hir::Statement::ModuleDeclaration(_)
+        | hir::Statement::Declaration(_) => {
+            // Leaf for this traversal's purposes, or declaration-shaped nodes a consumer can
+            // walk separately; nothing here recurses into an `hir::Expression`/`hir::Statement`.
+        }
+    }
+}
+
+pub fn walk_expression<'a, V: Visitor<'a>>(visitor: &mut V, expr: &hir::Expression<'a>) {
+    match expr {
+        hir::Expression::BinaryExpression(expr) => {
+            visitor.visit_binary_expression(&expr.left, expr.operator, &expr.right);
+        }
+        hir::Expression::LogicalExpression(expr) => {
+            visitor.visit_logical_expression(&expr.left, expr.operator, &expr.right);
+        }
+        hir::Expression::UnaryExpression(expr) => visitor.visit_unary_expression(&expr.argument),
+        hir::Expression::UpdateExpression(expr) => match &expr.argument {
+            hir::SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                visitor.visit_identifier_reference(ident);
+            }
+            hir::SimpleAssignmentTarget::MemberAssignmentTarget(member) => match member {
+                hir::MemberExpression::ComputedMemberExpression(expr) => {
+                    visitor.visit_member_expression(&expr.object);
+                    visitor.visit_expression(&expr.expression);
+                }
+                hir::MemberExpression::StaticMemberExpression(expr) => {
+                    visitor.visit_member_expression(&expr.object);
+                }
+                hir::MemberExpression::PrivateFieldExpression(expr) => {
+                    visitor.visit_member_expression(&expr.object);
+                }
+            },
+        },
+        hir::Expression::AssignmentExpression(expr) => {
+            visitor.visit_assignment_expression(&expr.right);
+        }
+        hir::Expression::ConditionalExpression(expr) => visitor.visit_conditional_expression(
+            &expr.test,
+            &expr.consequent,
+            &expr.alternate,
+        ),
+        hir::Expression::CallExpression(expr) => {
+            visitor.visit_call_expression(&expr.callee, &expr.arguments);
+        }
+        hir::Expression::NewExpression(expr) => {
+            visitor.visit_call_expression(&expr.callee, &expr.arguments);
+        }
+        hir::Expression::MemberExpression(expr) => match expr.as_ref() {
+            hir::MemberExpression::ComputedMemberExpression(expr) => {
+                visitor.visit_member_expression(&expr.object);
+                visitor.visit_expression(&expr.expression);
+            }
+            hir::MemberExpression::StaticMemberExpression(expr) => {
+                visitor.visit_member_expression(&expr.object);
+            }
+            hir::MemberExpression::PrivateFieldExpression(expr) => {
+                visitor.visit_member_expression(&expr.object);
+            }
+        },
+        hir::Expression::ArrayExpression(expr) => visitor.visit_array_expression(&expr.elements),
+        hir::Expression::ObjectExpression(expr) => {
+            visitor.visit_object_expression(&expr.properties);
+        }
+        hir::Expression::SequenceExpression(expr) => {
+            for expr in &expr.expressions {
+                visitor.visit_expression(expr);
+            }
+        }
+        hir::Expression::AwaitExpression(expr) => visitor.visit_expression(&expr.argument),
+        hir::Expression::YieldExpression(expr) => {
+            if let Some(argument) = &expr.argument {
+                visitor.visit_expression(argument);
+            }
+        }
+        hir::Expression::Identifier(ident) => visitor.visit_identifier_reference(ident),
+        hir::Expression::ChainExpression(expr) => match &expr.expression {
+            hir::ChainElement::CallExpression(expr) => {
+                visitor.visit_call_expression(&expr.callee, &expr.arguments);
+            }
+            hir::ChainElement::MemberExpression(expr) => match expr.as_ref() {
+                hir::MemberExpression::ComputedMemberExpression(expr) => {
+                    visitor.visit_member_expression(&expr.object);
+                    visitor.visit_expression(&expr.expression);
+                }
+                hir::MemberExpression::StaticMemberExpression(expr) => {
+                    visitor.visit_member_expression(&expr.object);
+                }
+                hir::MemberExpression::PrivateFieldExpression(expr) => {
+                    visitor.visit_member_expression(&expr.object);
+                }
+            },
+        },
+        // Leaves for this walk. Literals, `this`, `super` and meta-properties carry no child
+        // `hir::Expression`s; template literals, tagged templates, `#x in obj`, `import()`,
+        // functions and classes do, but this walk does not descend into them.
+        hir::Expression::BigintLiteral(_)
+        | hir::Expression::BooleanLiteral(_)
+        | hir::Expression::NullLiteral(_)
+        | hir::Expression::NumberLiteral(_)
+        | hir::Expression::RegExpLiteral(_)
+        | hir::Expression::StringLiteral(_)
+        | hir::Expression::TemplateLiteral(_)
+        | hir::Expression::ThisExpression(_)
+        | hir::Expression::Super(_)
+        | hir::Expression::MetaProperty(_)
+        | hir::Expression::PrivateInExpression(_)
+        | hir::Expression::TaggedTemplateExpression(_)
+        | hir::Expression::ImportExpression(_)
+        | hir::Expression::ArrowFunctionExpression(_)
+        | hir::Expression::FunctionExpression(_)
+        | hir::Expression::ClassExpression(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxc_allocator::Allocator;
+    use oxc_hir::hir;
+    use oxc_span::Span;
+
+    use super::Visitor;
+    use crate::AstLower;
+
+    #[derive(Default)]
+    struct RecordingVisitor {
+        visited: std::vec::Vec<std::string::String>,
+    }
+
+    impl<'a> Visitor<'a> for RecordingVisitor {
+        fn visit_identifier_reference(&mut self, ident: &hir::IdentifierReference) {
+            self.visited.push(ident.name.to_string());
+        }
+    }
+
+    fn identifier<'a>(lower: &mut AstLower<'a>, name: &str) -> hir::Expression<'a> {
+        let ident = lower.hir.identifier_reference(Span::default(), name.into());
+        lower.hir.identifier_reference_expression(ident)
+    }
+
+    #[test]
+    fn visits_computed_keys_and_values_and_spread_arguments() {
+        let allocator = Allocator::default();
+        let mut lower = AstLower::new(&allocator);
+
+        let key_expr = identifier(&mut lower, "computedKey");
+        let value_expr = identifier(&mut lower, "propValue");
+        let property = lower.hir.property(
+            Span::default(),
+            hir::PropertyKind::Init,
+            hir::PropertyKey::Expression(key_expr),
+            hir::PropertyValue::Expression(value_expr),
+            false,
+            false,
+            true,
+        );
+
+        let spread_argument = identifier(&mut lower, "spreadArg");
+        let spread = lower.hir.spread_element(Span::default(), spread_argument);
+
+        let mut properties = lower.hir.new_vec_with_capacity(2);
+        properties.push(hir::ObjectProperty::Property(property));
+        properties.push(hir::ObjectProperty::SpreadProperty(spread));
+
+        let mut visitor = RecordingVisitor::default();
+        visitor.visit_object_expression(&properties);
+
+        assert_eq!(visitor.visited, std::vec!["computedKey", "propValue", "spreadArg"]);
+    }
+}